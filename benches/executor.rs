@@ -0,0 +1,140 @@
+//! Benchmarks of the executors, on a deep solve.
+
+//  Features
+#![feature(generic_const_exprs)]
+#![feature(test)]
+//  Lints
+#![allow(incomplete_features)]
+
+extern crate test;
+
+use arcosphere::{
+    executor::{Executor, RayonExecutor, SequentialExecutor},
+    solver::{Solver, SolverConfiguration},
+    space_exploration::{SeArcosphereFamily, SeArcosphereSet},
+};
+use rayon::prelude::*;
+use test::{black_box, Bencher};
+
+#[bench]
+fn deep_sequential(bencher: &mut Bencher) {
+    deep::<SequentialExecutor>(bencher);
+}
+
+#[bench]
+fn deep_rayon(bencher: &mut Bencher) {
+    deep::<RayonExecutor>(bencher);
+}
+
+#[bench]
+fn deep_rayon_extend(bencher: &mut Bencher) {
+    deep::<ExtendExecutor>(bencher);
+}
+
+//  A single set of catalysts, hence the parallelism is within each searcher, between its forward & backward searches.
+#[bench]
+fn deep_catalysts_sequential(bencher: &mut Bencher) {
+    deep_catalysts::<SequentialExecutor>(bencher);
+}
+
+#[bench]
+fn deep_catalysts_rayon(bencher: &mut Bencher) {
+    deep_catalysts::<RayonExecutor>(bencher);
+}
+
+#[bench]
+fn deep_catalysts_rayon_extend(bencher: &mut Bencher) {
+    deep_catalysts::<ExtendExecutor>(bencher);
+}
+
+#[bench]
+fn deep_catalysts_rayon_concurrent(bencher: &mut Bencher) {
+    deep_catalysts_with::<RayonExecutor>(bencher, true);
+}
+
+//  Many trivial tasks, hence the overhead of the executor itself dominates.
+#[bench]
+fn trivial_rayon(bencher: &mut Bencher) {
+    trivial::<RayonExecutor>(bencher);
+}
+
+#[bench]
+fn trivial_rayon_extend(bencher: &mut Bencher) {
+    trivial::<ExtendExecutor>(bencher);
+}
+
+fn trivial<E>(bencher: &mut Bencher)
+where
+    E: Default + Executor,
+{
+    let executor = E::default();
+
+    bencher.iter(|| {
+        let tasks: Vec<_> = (0..TRIVIAL_TASKS).map(|i| move || black_box(i)).collect();
+
+        executor.execute(tasks).into_iter().sum::<usize>()
+    });
+}
+
+fn deep<E>(bencher: &mut Bencher)
+where
+    E: Default + Executor,
+{
+    let source: SeArcosphereSet = "OTX".parse().unwrap();
+    let target: SeArcosphereSet = "LOT".parse().unwrap();
+
+    let solver = Solver::<_, E>::new(SeArcosphereFamily);
+
+    bencher.iter(|| solver.solve(source, target).expect("success"));
+}
+
+fn deep_catalysts<E>(bencher: &mut Bencher)
+where
+    E: Default + Executor,
+{
+    deep_catalysts_with::<E>(bencher, false);
+}
+
+fn deep_catalysts_with<E>(bencher: &mut Bencher, concurrent_directions: bool)
+where
+    E: Default + Executor,
+{
+    let source: SeArcosphereSet = "OTX".parse().unwrap();
+    let target: SeArcosphereSet = "LOT".parse().unwrap();
+    let catalysts: SeArcosphereSet = "EEG".parse().unwrap();
+
+    let configuration = SolverConfiguration {
+        concurrent_directions,
+        ..SolverConfiguration::default()
+    };
+
+    let solver = Solver::<_, E>::new(SeArcosphereFamily).with_configuration(configuration);
+
+    bencher.iter(|| solver.solve_with_catalysts(source, target, catalysts).expect("success"));
+}
+
+//  The number of tasks of the trivial benchmarks, in the order of the number of sets of 4 catalysts.
+const TRIVIAL_TASKS: usize = 512;
+
+//  The alternative to the `RayonExecutor`, extending a buffer sized from the size hint of the tasks, rather than
+//  collecting the results.
+#[derive(Default)]
+struct ExtendExecutor;
+
+impl Executor for ExtendExecutor {
+    fn execute<I, F, R>(&self, tasks: I) -> impl IntoIterator<Item = R> + use<I, F, R>
+    where
+        I: IntoIterator<Item = F>,
+        F: FnOnce() -> R + Send,
+        R: Send,
+    {
+        let tasks = tasks.into_iter();
+
+        let mut results = Vec::with_capacity(tasks.size_hint().0);
+        let tasks: Vec<_> = tasks.collect();
+
+        results.par_extend(tasks.into_par_iter().map(|f| f()));
+
+        results
+    }
+}
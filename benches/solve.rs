@@ -0,0 +1,67 @@
+//! Benchmarks of the solver, on the documented Space Exploration scenarios.
+//!
+//! See `etc/space_exploration.md` for the scenarios.
+
+//  Features
+#![feature(generic_const_exprs)]
+#![feature(test)]
+//  Lints
+#![allow(incomplete_features)]
+
+extern crate test;
+
+use arcosphere::{
+    executor::DefaultExecutor,
+    solver::{Solver, SolverConfiguration},
+    space_exploration::{SeArcosphereFamily, SeArcosphereSet},
+};
+use test::Bencher;
+
+#[bench]
+fn dilation(bencher: &mut Bencher) {
+    scenario(bencher, "OZ", &["LL", "PP"]);
+}
+
+#[bench]
+fn folding(bencher: &mut Bencher) {
+    scenario(bencher, "LX", &["EP", "TZ"]);
+}
+
+#[bench]
+fn injection(bencher: &mut Bencher) {
+    scenario(bencher, "GT", &["ZZ", "EE"]);
+}
+
+#[bench]
+fn warping(bencher: &mut Bencher) {
+    scenario(bencher, "EP", &["TZ", "GO"]);
+}
+
+#[bench]
+fn deep(bencher: &mut Bencher) {
+    let configuration = SolverConfiguration {
+        maximum_recipes: 30,
+        ..SolverConfiguration::default()
+    };
+
+    let solver = Solver::<_, DefaultExecutor>::new(SeArcosphereFamily).with_configuration(configuration);
+
+    let source: SeArcosphereSet = "GT".parse().unwrap();
+    let target: SeArcosphereSet = "EE".parse().unwrap();
+
+    bencher.iter(|| solver.solve(source, target).expect("success"));
+}
+
+//  Solves each alternative of a scenario, at the default configuration.
+fn scenario(bencher: &mut Bencher, source: &str, targets: &[&str]) {
+    let solver = Solver::<_, DefaultExecutor>::new(SeArcosphereFamily);
+
+    let source: SeArcosphereSet = source.parse().unwrap();
+    let targets: Vec<SeArcosphereSet> = targets.iter().map(|target| target.parse().unwrap()).collect();
+
+    bencher.iter(|| {
+        for &target in &targets {
+            solver.solve(source, target).expect("success");
+        }
+    });
+}
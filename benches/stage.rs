@@ -0,0 +1,57 @@
+//! Benchmarks of the stages, on a 30-recipe stage.
+
+//  Features
+#![feature(generic_const_exprs)]
+#![feature(test)]
+//  Lints
+#![allow(incomplete_features)]
+
+extern crate test;
+
+use arcosphere::{
+    model::{ArcosphereRecipe, Stage},
+    space_exploration::{SeArcosphereRecipe, SeArcosphereSet},
+};
+use test::{black_box, Bencher};
+
+#[bench]
+fn input(bencher: &mut Bencher) {
+    let recipes = recipes();
+    let stage = Stage(&recipes[..]);
+
+    bencher.iter(|| {
+        let mut total = SeArcosphereSet::new();
+
+        for _ in 0..RUNS {
+            total += black_box(stage).input();
+        }
+
+        total
+    });
+}
+
+#[bench]
+fn input_into(bencher: &mut Bencher) {
+    let recipes = recipes();
+    let stage = Stage(&recipes[..]);
+
+    bencher.iter(|| {
+        let mut total = SeArcosphereSet::new();
+
+        for _ in 0..RUNS {
+            black_box(stage).input_into(&mut total);
+        }
+
+        total
+    });
+}
+
+//  The number of times the input of the stage is accumulated per iteration, low enough not to overflow.
+const RUNS: usize = 100;
+
+//  Cycles through all recipes, up to 30 of them.
+fn recipes() -> Vec<SeArcosphereRecipe> {
+    (0..30)
+        .map(|i| SeArcosphereRecipe::from_index(i % SeArcosphereRecipe::DIMENSION))
+        .collect()
+}
@@ -1,36 +1,81 @@
 //! Command parsing.
 
-use core::error::Error;
+use core::{error::Error, iter::Peekable, str::FromStr};
 
-use arcosphere::space_exploration::{SeArcosphereSet, SeStagedPath};
+use arcosphere::{
+    model::{ArcosphereFamily, SetParseError, StagedPath},
+    solver::SolverConfiguration,
+    space_exploration::SeArcosphereFamily,
+};
 
 /// Parses the command, returning it if valid.
-pub fn parse<I>(args: I) -> Result<Command, Box<dyn Error>>
+pub fn parse<F, I>(args: I) -> Result<Command<F>, Box<dyn Error>>
 where
+    F: ArcosphereFamily<Set: FromStr<Err = SetParseError>>,
     I: IntoIterator<Item = String>,
 {
     Command::parse(args)
 }
 
+/// Generic options, passed before the subcommand.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Options {
+    /// Path to the file defining the family, if not the default Space Exploration family.
+    pub family: Option<String>,
+}
+
+impl Options {
+    /// Parses the generic options, leaving the subcommand and its arguments in `args`.
+    pub fn parse<I>(args: &mut Peekable<I>) -> Result<Self, Box<dyn Error>>
+    where
+        I: Iterator<Item = String>,
+    {
+        let mut options = Self::default();
+
+        while args.next_if(|arg| matches!(arg.as_str(), "-f" | "--family")).is_some() {
+            let value = args
+                .next()
+                .ok_or("Specify the path to the family file after --family")?;
+
+            options.family = Some(value);
+        }
+
+        Ok(options)
+    }
+}
+
 /// Command passed to the binary.
 #[derive(Clone, Debug, PartialEq)]
-pub enum Command {
+pub enum Command<F = SeArcosphereFamily>
+where
+    F: ArcosphereFamily,
+{
     Help,
-    Solve {
-        source: SeArcosphereSet,
-        target: SeArcosphereSet,
-        plan: bool,
-        sort_by: SortBy,
-    },
+    Solve(SolveOptions<F>),
     Verify {
-        path: SeStagedPath,
+        path: Option<StagedPath<F>>,
+        stdin: bool,
+        trace: bool,
     },
     Plan {
-        path: SeStagedPath,
+        path: StagedPath<F>,
+        csv: bool,
+        fancy: bool,
+    },
+    Explore {
+        source: F::Set,
+        depth: u8,
+    },
+    Diff {
+        a: StagedPath<F>,
+        b: StagedPath<F>,
     },
 }
 
-impl Command {
+impl<F> Command<F>
+where
+    F: ArcosphereFamily<Set: FromStr<Err = SetParseError>>,
+{
     /// Parses the command, returning it if valid.
     pub fn parse<I>(args: I) -> Result<Self, Box<dyn Error>>
     where
@@ -38,18 +83,43 @@ impl Command {
     {
         let mut args = args.into_iter();
 
-        let subcommand = args.next().ok_or("Select a subcommand: solve, verify or plan")?;
+        let subcommand = args
+            .next()
+            .ok_or("Select a subcommand: solve, verify, plan, explore or diff")?;
 
         match subcommand.as_str() {
             "-h" | "--help" => Ok(Self::Help),
             "solve" => Self::parse_solve(args),
             "verify" => Self::parse_verify(args),
             "plan" => Self::parse_plan(args),
-            _ => Err(format!("Unknown subcommand {subcommand}, only solve, verify and plan are accepted").into()),
+            "explore" => Self::parse_explore(args),
+            "diff" => Self::parse_diff(args),
+            _ => Err(format!(
+                "Unknown subcommand {subcommand}, only solve, verify, plan, explore and diff are accepted"
+            )
+            .into()),
         }
     }
 }
 
+/// Options of the solve subcommand.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolveOptions<F = SeArcosphereFamily>
+where
+    F: ArcosphereFamily,
+{
+    pub source: F::Set,
+    pub target: F::Set,
+    pub plan: bool,
+    pub sort_by: SortBy,
+    pub format: Format,
+    pub limit: Option<usize>,
+    pub no_catalysts: bool,
+    pub timeout: Option<u64>,
+    pub have: Option<F::Set>,
+    pub fancy: bool,
+}
+
 /// Plan execution on output of solve subcommand.
 #[derive(Clone, Debug, Default, PartialEq)]
 pub enum SortBy {
@@ -58,75 +128,231 @@ pub enum SortBy {
     Stages,
     /// Sort by number of recipes (lowest first).
     Recipes,
+    /// Sort by number of inversion recipes (lowest first).
+    Inversions,
+}
+
+/// Output format of solve subcommand.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Format {
+    /// Human readable text, one path per line.
+    #[default]
+    Text,
+    /// JSON, an array of paths.
+    Json,
 }
 
 //
 //  Implementation
 //
 
-impl Command {
-    fn parse_solve<I>(args: I) -> Result<Self, Box<dyn Error>>
+impl<F> Command<F>
+where
+    F: ArcosphereFamily<Set: FromStr<Err = SetParseError>>,
+{
+    fn parse_solve<I>(mut args: I) -> Result<Self, Box<dyn Error>>
     where
         I: Iterator<Item = String>,
     {
-        let mut args = args.peekable();
-
+        let mut positionals = Vec::new();
         let mut plan = false;
         let mut sort_by = SortBy::default();
-
-        while let Some(option) = args.next_if(|arg| arg.starts_with('-')) {
-            match option.as_str() {
+        let mut format = Format::default();
+        let mut limit = None;
+        let mut no_catalysts = false;
+        let mut timeout = None;
+        let mut have = None;
+        let mut fancy = false;
+
+        //  As with explore, the options may follow the positional arguments, as in `solve SOURCE TARGET --timeout 10`.
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-a" | "--have" => {
+                    let value = args
+                        .next()
+                        .ok_or("Specify the set of available catalysts after --have")?;
+
+                    have = Some(value);
+                }
+                "--fancy" => fancy = true,
+                "-j" | "--json" => format = Format::Json,
+                "-l" | "--limit" => {
+                    let value = args.next().ok_or("Specify the maximum number of paths after --limit")?;
+
+                    let value = value
+                        .parse()
+                        .map_err(|e| format!("Failed to parse LIMIT {value}: {e}"))?;
+
+                    limit = Some(value);
+                }
+                "-n" | "--no-catalysts" => no_catalysts = true,
                 "-p" | "--plan" => plan = true,
+                "-i" | "--sort-inversions" => sort_by = SortBy::Inversions,
                 "-s" | "--sort-stages" => sort_by = SortBy::Stages,
                 "-r" | "--sort-recipes" => sort_by = SortBy::Recipes,
-                _ => return Err(format!("Unknown option '{option}'").into()),
+                "-t" | "--timeout" => {
+                    let value = args.next().ok_or("Specify the number of seconds after --timeout")?;
+
+                    let value = value
+                        .parse()
+                        .map_err(|e| format!("Failed to parse SECONDS {value}: {e}"))?;
+
+                    timeout = Some(value);
+                }
+                _ if arg.starts_with('-') => return Err(format!("Unknown option '{arg}'").into()),
+                _ => positionals.push(arg),
             }
         }
 
-        let (Some(source), Some(target), None) = (args.next(), args.next(), args.next()) else {
+        let Ok([source, target]) = <[String; 2]>::try_from(positionals) else {
             return Err("Specify exactly two positional arguments to solve: [OPTIONS] SOURCE and TARGET".into());
         };
 
-        let source: SeArcosphereSet = source
+        if plan && format == Format::Json {
+            return Err("The plan option cannot be combined with the json option".into());
+        }
+
+        if fancy && format == Format::Json {
+            return Err("The fancy option cannot be combined with the json option".into());
+        }
+
+        let source: F::Set = source
             .parse()
             .map_err(|e| format!("Failed to parse SOURCE {source}: {e}"))?;
 
-        let target: SeArcosphereSet = target
+        let target: F::Set = target
             .parse()
             .map_err(|e| format!("Failed to parse TARGET {target}: {e}"))?;
 
-        Ok(Self::Solve {
+        let have: Option<F::Set> = have
+            .map(|have| have.parse().map_err(|e| format!("Failed to parse HAVE {have}: {e}")))
+            .transpose()?;
+
+        Ok(Self::Solve(SolveOptions {
             source,
             target,
             plan,
             sort_by,
-        })
+            format,
+            limit,
+            no_catalysts,
+            timeout,
+            have,
+            fancy,
+        }))
     }
 
-    fn parse_verify<I>(mut args: I) -> Result<Self, Box<dyn Error>>
+    fn parse_verify<I>(args: I) -> Result<Self, Box<dyn Error>>
     where
         I: Iterator<Item = String>,
     {
+        let mut args = args.peekable();
+
+        let trace = args.next_if(|arg| matches!(arg.as_str(), "-t" | "--trace")).is_some();
+
         let Some(path) = args.next() else {
-            return Err("Specify exactly one argument to verify: PATH".into());
+            return Err("Specify exactly one argument to verify: [--trace] PATH, or - to read from stdin".into());
         };
 
+        if path == "-" || path == "--stdin" {
+            return Ok(Self::Verify {
+                path: None,
+                stdin: true,
+                trace,
+            });
+        }
+
         let path = path.parse().map_err(|e| format!("Failed to parse PATH: {e}"))?;
 
-        Ok(Self::Verify { path })
+        Ok(Self::Verify {
+            path: Some(path),
+            stdin: false,
+            trace,
+        })
     }
 
-    fn parse_plan<I>(mut args: I) -> Result<Self, Box<dyn Error>>
+    fn parse_plan<I>(args: I) -> Result<Self, Box<dyn Error>>
     where
         I: Iterator<Item = String>,
     {
-        let Some(path) = args.next() else {
-            return Err("Specify exactly one argument to plan: PATH".into());
+        let mut path = None;
+        let mut csv = false;
+        let mut fancy = false;
+
+        //  As with explore, the options may follow the path, as in `plan PATH --csv`.
+        for arg in args {
+            match arg.as_str() {
+                "-c" | "--csv" => csv = true,
+                "--fancy" => fancy = true,
+                _ if arg.starts_with('-') => return Err(format!("Unknown option '{arg}'").into()),
+                _ if path.is_none() => path = Some(arg),
+                _ => return Err("Specify exactly one argument to plan: [OPTIONS] PATH".into()),
+            }
+        }
+
+        let Some(path) = path else {
+            return Err("Specify exactly one argument to plan: [OPTIONS] PATH".into());
         };
 
+        if csv && fancy {
+            return Err("The fancy option cannot be combined with the csv option".into());
+        }
+
         let path = path.parse().map_err(|e| format!("Failed to parse PATH: {e}"))?;
 
-        Ok(Self::Plan { path })
+        Ok(Self::Plan { path, csv, fancy })
+    }
+
+    fn parse_explore<I>(args: I) -> Result<Self, Box<dyn Error>>
+    where
+        I: Iterator<Item = String>,
+    {
+        let mut args = args.peekable();
+
+        let mut source = None;
+        let mut depth = SolverConfiguration::default().maximum_recipes;
+
+        //  Unlike other subcommands, the option may follow the source, as in `explore SOURCE --depth N`.
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-d" | "--depth" => {
+                    let value = args
+                        .next()
+                        .ok_or("Specify the maximum number of recipes after --depth")?;
+
+                    depth = value
+                        .parse()
+                        .map_err(|e| format!("Failed to parse DEPTH {value}: {e}"))?;
+                }
+                _ if arg.starts_with('-') => return Err(format!("Unknown option '{arg}'").into()),
+                _ if source.is_none() => source = Some(arg),
+                _ => return Err("Specify exactly one positional argument to explore: [OPTIONS] SOURCE".into()),
+            }
+        }
+
+        let Some(source) = source else {
+            return Err("Specify exactly one positional argument to explore: [OPTIONS] SOURCE".into());
+        };
+
+        let source: F::Set = source
+            .parse()
+            .map_err(|e| format!("Failed to parse SOURCE {source}: {e}"))?;
+
+        Ok(Self::Explore { source, depth })
+    }
+
+    fn parse_diff<I>(mut args: I) -> Result<Self, Box<dyn Error>>
+    where
+        I: Iterator<Item = String>,
+    {
+        let (Some(a), Some(b), None) = (args.next(), args.next(), args.next()) else {
+            return Err("Specify exactly two arguments to diff: PATH and PATH".into());
+        };
+
+        let a = a.parse().map_err(|e| format!("Failed to parse first PATH: {e}"))?;
+        let b = b.parse().map_err(|e| format!("Failed to parse second PATH: {e}"))?;
+
+        Ok(Self::Diff { a, b })
     }
 }
 
@@ -134,7 +360,7 @@ impl Command {
 mod tests {
     use core::num::NonZeroU8;
 
-    use arcosphere::space_exploration::{SeArcosphereRecipe, SePath, SeStagedPath};
+    use arcosphere::space_exploration::{SeArcosphereRecipe, SeArcosphereSet, SePath, SeStagedPath};
 
     use super::*;
 
@@ -147,24 +373,151 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn parse_options() {
+        let mut args = ["--family", "my_mod.toml", "solve", "EP", "LX"]
+            .map(String::from)
+            .into_iter()
+            .peekable();
+
+        let options = Options::parse(&mut args).expect("success");
+
+        assert_eq!(Some("my_mod.toml"), options.family.as_deref());
+        assert_eq!(Some("solve"), args.peek().map(String::as_str));
+
+        let mut args = ["--family"].map(String::from).into_iter().peekable();
+
+        assert!(Options::parse(&mut args).is_err());
+    }
+
     #[test]
     fn parse_solve() {
-        let expected = Command::Solve {
-            source: "EP".parse().unwrap(),
-            target: "LX".parse().unwrap(),
-            plan: false,
-            sort_by: SortBy::Stages,
-        };
+        let expected = Command::Solve(default_solve());
 
         let command = parse_command(&["solve", "EP", "LX"]).expect("success");
 
         assert_eq!(expected, command);
     }
 
+    #[test]
+    fn parse_solve_json() {
+        let expected = Command::Solve(SolveOptions {
+            format: Format::Json,
+            ..default_solve()
+        });
+
+        let command = parse_command(&["solve", "--json", "EP", "LX"]).expect("success");
+
+        assert_eq!(expected, command);
+
+        let result = parse_command(&["solve", "--json", "--plan", "EP", "LX"]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_solve_limit() {
+        let expected = Command::Solve(SolveOptions {
+            sort_by: SortBy::Recipes,
+            limit: Some(5),
+            ..default_solve()
+        });
+
+        let command = parse_command(&["solve", "--limit", "5", "-r", "EP", "LX"]).expect("success");
+
+        assert_eq!(expected, command);
+
+        let result = parse_command(&["solve", "--limit", "EP", "LX"]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_solve_no_catalysts() {
+        let expected = Command::Solve(SolveOptions {
+            no_catalysts: true,
+            ..default_solve()
+        });
+
+        for option in ["-n", "--no-catalysts"] {
+            let command = parse_command(&["solve", option, "EP", "LX"]).expect("success");
+
+            assert_eq!(expected, command);
+        }
+    }
+
+    #[test]
+    fn parse_solve_sort_inversions() {
+        let expected = Command::Solve(SolveOptions {
+            sort_by: SortBy::Inversions,
+            ..default_solve()
+        });
+
+        let command = parse_command(&["solve", "--sort-inversions", "EP", "LX"]).expect("success");
+
+        assert_eq!(expected, command);
+    }
+
+    #[test]
+    fn parse_solve_timeout() {
+        let expected = Command::Solve(SolveOptions {
+            timeout: Some(10),
+            ..default_solve()
+        });
+
+        let command = parse_command(&["solve", "EP", "LX", "--timeout", "10"]).expect("success");
+
+        assert_eq!(expected, command);
+
+        let result = parse_command(&["solve", "EP", "LX", "--timeout"]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_solve_have() {
+        let expected = Command::Solve(SolveOptions {
+            have: Some("GO".parse().unwrap()),
+            ..default_solve()
+        });
+
+        let command = parse_command(&["solve", "EP", "LX", "--have", "GO"]).expect("success");
+
+        assert_eq!(expected, command);
+
+        let command = parse_command(&["solve", "EP", "LX", "--have", "GO", "--timeout", "10"]).expect("success");
+
+        assert!(matches!(
+            command,
+            Command::Solve(SolveOptions {
+                have: Some(_),
+                timeout: Some(10),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_solve_fancy() {
+        let expected = Command::Solve(SolveOptions {
+            plan: true,
+            fancy: true,
+            ..default_solve()
+        });
+
+        let command = parse_command(&["solve", "EP", "LX", "--fancy", "--plan"]).expect("success");
+
+        assert_eq!(expected, command);
+
+        let result = parse_command(&["solve", "EP", "LX", "--fancy", "--json"]);
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn parse_verify_minimal() {
         let expected = Command::Verify {
-            path: SeStagedPath {
+            path: Some(SeStagedPath {
                 path: SePath {
                     source: "PG".parse().unwrap(),
                     target: "XO".parse().unwrap(),
@@ -173,7 +526,9 @@ mod tests {
                     recipes: vec![SeArcosphereRecipe::PG],
                 },
                 stages: Vec::new(),
-            },
+            }),
+            stdin: false,
+            trace: false,
         };
 
         let command = parse_command(&["verify", "PG -> XO => PG -> XO"]).expect("success");
@@ -184,7 +539,7 @@ mod tests {
     #[test]
     fn parse_verify_complete() {
         let expected = Command::Verify {
-            path: SeStagedPath {
+            path: Some(SeStagedPath {
                 path: SePath {
                     source: "EP".parse().unwrap(),
                     target: "LX".parse().unwrap(),
@@ -193,7 +548,9 @@ mod tests {
                     recipes: vec![SeArcosphereRecipe::PG, SeArcosphereRecipe::EO],
                 },
                 stages: vec![1],
-            },
+            }),
+            stdin: false,
+            trace: false,
         };
 
         let command = parse_command(&["verify", "EP -> LX x2 + G => PG -> XO | EO -> LG"]).expect("success");
@@ -201,6 +558,159 @@ mod tests {
         assert_eq!(expected, command);
     }
 
+    #[test]
+    fn parse_verify_stdin() {
+        let expected = Command::Verify {
+            path: None,
+            stdin: true,
+            trace: false,
+        };
+
+        for argument in ["-", "--stdin"] {
+            let command = parse_command(&["verify", argument]).expect("success");
+
+            assert_eq!(expected, command);
+        }
+    }
+
+    #[test]
+    fn parse_verify_trace() {
+        let expected = Command::Verify {
+            path: None,
+            stdin: true,
+            trace: true,
+        };
+
+        for argument in ["-t", "--trace"] {
+            let command = parse_command(&["verify", argument, "-"]).expect("success");
+
+            assert_eq!(expected, command);
+        }
+
+        let result = parse_command(&["verify", "--trace"]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_plan_csv() {
+        let path: SeStagedPath = "GLZ -> LOT + P => GP -> OX | XZ -> PT".parse().unwrap();
+
+        let command = parse_command(&["plan", &path.to_string()]).expect("success");
+
+        assert_eq!(
+            Command::Plan {
+                path: path.clone(),
+                csv: false,
+                fancy: false,
+            },
+            command
+        );
+
+        for command in [
+            &["plan", "--csv", &path.to_string()],
+            &["plan", &path.to_string(), "-c"],
+        ] {
+            let command = parse_command(command).expect("success");
+
+            assert_eq!(
+                Command::Plan {
+                    path: path.clone(),
+                    csv: true,
+                    fancy: false,
+                },
+                command
+            );
+        }
+
+        assert!(parse_command(&["plan", "--csv"]).is_err());
+    }
+
+    #[test]
+    fn parse_plan_fancy() {
+        let path: SeStagedPath = "GLZ -> LOT + P => GP -> OX | XZ -> PT".parse().unwrap();
+
+        let command = parse_command(&["plan", &path.to_string(), "--fancy"]).expect("success");
+
+        assert_eq!(
+            Command::Plan {
+                path: path.clone(),
+                csv: false,
+                fancy: true,
+            },
+            command
+        );
+
+        assert!(parse_command(&["plan", "--fancy", "--csv", &path.to_string()]).is_err());
+    }
+
+    #[test]
+    fn parse_explore() {
+        let expected = Command::Explore {
+            source: "EP".parse().unwrap(),
+            depth: 3,
+        };
+
+        for command in [&["explore", "EP", "--depth", "3"], &["explore", "-d", "3", "EP"]] {
+            let command = parse_command(command).expect("success");
+
+            assert_eq!(expected, command);
+        }
+
+        let command = parse_command(&["explore", "EP"]).expect("success");
+
+        assert_eq!(
+            Command::Explore {
+                source: "EP".parse().unwrap(),
+                depth: SolverConfiguration::default().maximum_recipes,
+            },
+            command
+        );
+
+        for invalid in [
+            &["explore"][..],
+            &["explore", "EP", "LX"],
+            &["explore", "EP", "--depth"],
+        ] {
+            assert!(parse_command(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn parse_diff() {
+        let a: SeStagedPath = "EP -> LX x2 + G => PG -> XO | EO -> LG".parse().unwrap();
+        let b: SeStagedPath = "EP -> LX + GO => EO -> GL // GP -> OX".parse().unwrap();
+
+        let expected = Command::Diff {
+            a: a.clone(),
+            b: b.clone(),
+        };
+
+        let command = parse_command(&["diff", &a.to_string(), &b.to_string()]).expect("success");
+
+        assert_eq!(expected, command);
+
+        for invalid in [&["diff"][..], &["diff", "EP -> LX + GO => EO -> GL // GP -> OX"]] {
+            assert!(parse_command(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
+    //  The options of `solve EP LX`, overridden by each test.
+    fn default_solve() -> SolveOptions {
+        SolveOptions {
+            source: "EP".parse().unwrap(),
+            target: "LX".parse().unwrap(),
+            plan: false,
+            sort_by: SortBy::Stages,
+            format: Format::Text,
+            limit: None,
+            no_catalysts: false,
+            timeout: None,
+            have: None,
+            fancy: false,
+        }
+    }
+
     fn parse_command(command: &[&str]) -> Result<Command, Box<dyn Error>> {
         Command::parse(command.iter().map(|s| String::from(*s)))
     }
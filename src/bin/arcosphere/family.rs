@@ -0,0 +1,262 @@
+//! Family file parsing.
+//!
+//! A family file is a small subset of TOML, listing the arcospheres then the recipes of the family:
+//!
+//! ```toml
+//! [[arcosphere]]
+//! abbr = "E"             # A single alphabetic character.
+//! full = "Epsilon"
+//! fancy = "ε"            # Optional, defaults to the full name.
+//! polarity = "negative"  # Either "negative" or "positive".
+//!
+//! [[recipe]]
+//! input = "EO"
+//! output = "GL"
+//! ```
+//!
+//! Only tables of arrays, comments, and key-value pairs with basic strings are supported.
+
+use core::error::Error;
+
+use std::fs;
+
+use arcosphere::{
+    dynamic::{DynArcosphereDefinition, DynFamilyDefinition},
+    model::Polarity,
+};
+
+/// Loads the family definition from the file at `path`.
+pub fn load(path: &str) -> Result<DynFamilyDefinition, Box<dyn Error>> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read FAMILY {path}: {e}"))?;
+
+    parse(&content).map_err(|e| format!("Failed to parse FAMILY {path}: {e}").into())
+}
+
+/// Parses the family definition.
+pub fn parse(content: &str) -> Result<DynFamilyDefinition, Box<dyn Error>> {
+    let mut tables: Vec<(usize, Table)> = Vec::new();
+
+    for (number, line) in content.lines().enumerate() {
+        let number = number + 1;
+        let line = strip_comment(line).trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix("[[").and_then(|line| line.strip_suffix("]]")) {
+            let table = match header.trim() {
+                "arcosphere" => Table::Arcosphere(Vec::new()),
+                "recipe" => Table::Recipe(Vec::new()),
+                header => return Err(format!("Unknown table '{header}' on line {number}").into()),
+            };
+
+            tables.push((number, table));
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| format!("Expected 'key = \"value\"' on line {number}"))?;
+
+        let (key, value) = (key.trim(), parse_string(value.trim(), number)?);
+
+        let Some((_, table)) = tables.last_mut() else {
+            return Err(format!("Expected [[arcosphere]] or [[recipe]] before line {number}").into());
+        };
+
+        let entries = match table {
+            Table::Arcosphere(entries) | Table::Recipe(entries) => entries,
+        };
+
+        if entries.iter().any(|(k, _)| *k == key) {
+            return Err(format!("Duplicate key '{key}' on line {number}").into());
+        }
+
+        entries.push((key, value));
+    }
+
+    let mut definition = DynFamilyDefinition::default();
+
+    for (number, table) in tables {
+        match table {
+            Table::Arcosphere(mut entries) => {
+                let abbr = take(&mut entries, "abbr", number)?;
+                let full = take(&mut entries, "full", number)?;
+                let fancy = take(&mut entries, "fancy", number).ok();
+                let polarity = take(&mut entries, "polarity", number)?;
+
+                let mut chars = abbr.chars();
+
+                let (Some(abbr), None) = (chars.next(), chars.next()) else {
+                    return Err(format!("Expected a single character abbr in table on line {number}").into());
+                };
+
+                if !abbr.is_alphabetic() {
+                    return Err(format!("Expected an alphabetic abbr, not '{abbr}', in table on line {number}").into());
+                }
+
+                let polarity = match polarity.as_str() {
+                    "negative" => Polarity::Negative,
+                    "positive" => Polarity::Positive,
+                    _ => return Err(format!("Unknown polarity '{polarity}' in table on line {number}").into()),
+                };
+
+                check_empty(&entries, number)?;
+
+                definition.arcospheres.push(DynArcosphereDefinition {
+                    abbr,
+                    full,
+                    fancy,
+                    polarity,
+                });
+            }
+            Table::Recipe(mut entries) => {
+                let input = take(&mut entries, "input", number)?;
+                let output = take(&mut entries, "output", number)?;
+
+                check_empty(&entries, number)?;
+
+                definition.recipes.push((input, output));
+            }
+        }
+    }
+
+    Ok(definition)
+}
+
+//
+//  Implementation
+//
+
+enum Table<'a> {
+    Arcosphere(Vec<(&'a str, String)>),
+    Recipe(Vec<(&'a str, String)>),
+}
+
+//  Strips the comment, if any, from the line.
+//
+//  A `#` within a string is not a comment.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => (),
+        }
+    }
+
+    line
+}
+
+//  Parses a basic string, ie "...", with the \" and \\ escapes.
+fn parse_string(value: &str, number: usize) -> Result<String, Box<dyn Error>> {
+    let Some(inner) = value.strip_prefix('"').and_then(|value| value.strip_suffix('"')) else {
+        return Err(format!("Expected a quoted string on line {number}").into());
+    };
+
+    let mut result = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(c @ ('"' | '\\')) => result.push(c),
+                _ => return Err(format!("Unsupported escape sequence on line {number}").into()),
+            },
+            '"' => return Err(format!("Unexpected quote on line {number}").into()),
+            c => result.push(c),
+        }
+    }
+
+    Ok(result)
+}
+
+fn take(entries: &mut Vec<(&str, String)>, key: &str, number: usize) -> Result<String, Box<dyn Error>> {
+    let index = entries
+        .iter()
+        .position(|(k, _)| *k == key)
+        .ok_or_else(|| format!("Missing key '{key}' in table on line {number}"))?;
+
+    Ok(entries.swap_remove(index).1)
+}
+
+fn check_empty(entries: &[(&str, String)], number: usize) -> Result<(), Box<dyn Error>> {
+    match entries.first() {
+        Some((key, _)) => Err(format!("Unknown key '{key}' in table on line {number}").into()),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_minimal() {
+        let content = r#"
+            # Two arcospheres.
+            [[arcosphere]]
+            abbr = "A"
+            full = "Alpha"
+            fancy = "α"
+            polarity = "negative"
+
+            [[arcosphere]]
+            abbr = "B"
+            full = "Beta # Not a comment" # A comment.
+            polarity = "positive"
+
+            [[recipe]]
+            input = "AA"
+            output = "BB"
+        "#;
+
+        let expected = DynFamilyDefinition {
+            arcospheres: vec![
+                DynArcosphereDefinition {
+                    abbr: 'A',
+                    full: "Alpha".to_string(),
+                    fancy: Some("α".to_string()),
+                    polarity: Polarity::Negative,
+                },
+                DynArcosphereDefinition {
+                    abbr: 'B',
+                    full: "Beta # Not a comment".to_string(),
+                    fancy: None,
+                    polarity: Polarity::Positive,
+                },
+            ],
+            recipes: vec![("AA".to_string(), "BB".to_string())],
+        };
+
+        let definition = parse(content).expect("success");
+
+        assert_eq!(expected, definition);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        const INVALID: [&str; 8] = [
+            "abbr = \"A\"",
+            "[[sphere]]",
+            "[[recipe]]\ninput = \"AA\"",
+            "[[recipe]]\ninput = \"AA\"\noutput = \"BB\"\nextra = \"C\"",
+            "[[recipe]]\ninput = AA\noutput = \"BB\"",
+            "[[arcosphere]]\nabbr = \"A\"\nfull = \"Alpha\"\npolarity = \"neutral\"",
+            "[[arcosphere]]\nabbr = \"AB\"\nfull = \"Alpha\"\npolarity = \"negative\"",
+            "[[arcosphere]]\nabbr = \",\"\nfull = \"Comma\"\npolarity = \"negative\"",
+        ];
+
+        for content in INVALID {
+            let result = parse(content);
+
+            assert!(result.is_err(), "{content}");
+        }
+    }
+} // mod tests
@@ -0,0 +1,495 @@
+//! JSON output.
+//!
+//! A minimal JSON serializer, for the `serde` representations of the library, so the output is exactly what any serde
+//! JSON serializer would produce, except for a space after each `:` and `,` for readability.
+
+use core::{
+    error,
+    fmt::{self, Write},
+};
+
+use serde::ser::{self, Serialize};
+
+/// Formats the value as JSON.
+pub fn to_string<T>(value: &T) -> Result<String, Error>
+where
+    T: ?Sized + Serialize,
+{
+    let mut serializer = Serializer { output: String::new() };
+
+    value.serialize(&mut serializer)?;
+
+    Ok(serializer.output)
+}
+
+/// Error which may occur when formatting as JSON.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&self.0)
+    }
+}
+
+impl error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T>(message: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self(message.to_string())
+    }
+}
+
+//
+//  Implementation
+//
+
+struct Serializer {
+    output: String,
+}
+
+impl Serializer {
+    //  Writing to a `String` never fails.
+    fn push_display<T>(&mut self, value: T)
+    where
+        T: fmt::Display,
+    {
+        let _ = write!(self.output, "{value}");
+    }
+
+    fn push_string(&mut self, value: &str) {
+        self.output.push('"');
+
+        for c in value.chars() {
+            match c {
+                '"' => self.output.push_str("\\\""),
+                '\\' => self.output.push_str("\\\\"),
+                '\n' => self.output.push_str("\\n"),
+                '\r' => self.output.push_str("\\r"),
+                '\t' => self.output.push_str("\\t"),
+                c if c.is_control() => self.push_display(format_args!("\\u{:04x}", c as u32)),
+                c => self.output.push(c),
+            }
+        }
+
+        self.output.push('"');
+    }
+
+    //  Opens a compound, closed by `close`.
+    fn open(&mut self, open: &str, close: &'static str) -> Compound<'_> {
+        self.output.push_str(open);
+
+        Compound {
+            serializer: self,
+            first: true,
+            close,
+        }
+    }
+
+    //  Opens a compound within an object with a single `variant` key, as serde represents enum variants by default.
+    fn open_variant(&mut self, variant: &str, open: &str, close: &'static str) -> Compound<'_> {
+        self.output.push('{');
+        self.push_string(variant);
+        self.output.push_str(": ");
+
+        self.open(open, close)
+    }
+}
+
+//  An array or object, being serialized.
+struct Compound<'a> {
+    serializer: &'a mut Serializer,
+    first: bool,
+    close: &'static str,
+}
+
+impl Compound<'_> {
+    fn element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.separate();
+
+        value.serialize(&mut *self.serializer)
+    }
+
+    fn field<T>(&mut self, key: &str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.separate();
+
+        self.serializer.push_string(key);
+        self.serializer.output.push_str(": ");
+
+        value.serialize(&mut *self.serializer)
+    }
+
+    fn separate(&mut self) {
+        if !self.first {
+            self.serializer.output.push_str(", ");
+        }
+
+        self.first = false;
+    }
+
+    fn close(self) -> Result<(), Error> {
+        self.serializer.output.push_str(self.close);
+
+        Ok(())
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self, value: bool) -> Result<(), Error> {
+        self.push_display(value);
+
+        Ok(())
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<(), Error> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<(), Error> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<(), Error> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<(), Error> {
+        self.push_display(value);
+
+        Ok(())
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<(), Error> {
+        self.serialize_u64(value.into())
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<(), Error> {
+        self.serialize_u64(value.into())
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<(), Error> {
+        self.serialize_u64(value.into())
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<(), Error> {
+        self.push_display(value);
+
+        Ok(())
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<(), Error> {
+        self.serialize_f64(value.into())
+    }
+
+    //  As serde_json, non-finite numbers are serialized as null.
+    fn serialize_f64(self, value: f64) -> Result<(), Error> {
+        if value.is_finite() {
+            self.push_display(value);
+        } else {
+            self.output.push_str("null");
+        }
+
+        Ok(())
+    }
+
+    fn serialize_char(self, value: char) -> Result<(), Error> {
+        self.push_string(value.encode_utf8(&mut [0; 4]));
+
+        Ok(())
+    }
+
+    fn serialize_str(self, value: &str) -> Result<(), Error> {
+        self.push_string(value);
+
+        Ok(())
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<(), Error> {
+        ser::Serializer::collect_seq(self, value)
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.output.push_str("null");
+
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<(), Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let mut compound = self.open_variant(variant, "", "}");
+
+        compound.element(value)?;
+        compound.close()
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'a>, Error> {
+        Ok(self.open("[", "]"))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Compound<'a>, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Compound<'a>, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, Error> {
+        Ok(self.open_variant(variant, "[", "]}"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'a>, Error> {
+        Ok(self.open("{", "}"))
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Compound<'a>, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, Error> {
+        Ok(self.open_variant(variant, "{", "}}"))
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    //  JSON keys are strings, hence any other key is rejected.
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let start = self.serializer.output.len();
+
+        self.element(key)?;
+
+        if !self.serializer.output[start..].ends_with('"') {
+            return Err(Error("Expected a string key".to_string()));
+        }
+
+        self.serializer.output.push_str(": ");
+
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut *self.serializer)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::Serialize;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    enum Shape {
+        Unit,
+        Newtype(u8),
+        Tuple(u8, i8),
+        Struct { name: &'static str },
+    }
+
+    #[test]
+    fn to_string_escaped() {
+        assert_eq!(Ok(r#""a\"b\\c\nd\u0001""#.to_string()), to_string("a\"b\\c\nd\u{1}"));
+        assert_eq!(Ok(r#""ε""#.to_string()), to_string(&'ε'));
+    }
+
+    #[test]
+    fn to_string_compound() {
+        assert_eq!(
+            Ok("[1, -2, null, true]".to_string()),
+            to_string(&(1u8, -2i64, None::<u8>, true))
+        );
+
+        let map = BTreeMap::from([("a", 1), ("b", 2)]);
+
+        assert_eq!(Ok(r#"{"a": 1, "b": 2}"#.to_string()), to_string(&map));
+        assert!(to_string(&BTreeMap::from([(1, 2)])).is_err());
+
+        let shapes = [
+            Shape::Unit,
+            Shape::Newtype(1),
+            Shape::Tuple(2, -3),
+            Shape::Struct { name: "x" },
+        ];
+
+        let expected = r#"["Unit", {"Newtype": 1}, {"Tuple": [2, -3]}, {"Struct": {"name": "x"}}]"#;
+
+        assert_eq!(Ok(expected.to_string()), to_string(&shapes));
+    }
+} // mod tests
@@ -1,12 +1,17 @@
 //!  CLI wrapper around the library functionality, for human explorations.
 //!
-//! There are two sub-commands:
+//! There are five sub-commands:
 //!
-//! -   `<arcosphere> solve SOURCE TARGET`.
-//! -   `<arcosphere> verify PATH`.
+//! -   `<arcosphere> solve SOURCE TARGET [--have HAVE] [--limit N] [--no-catalysts] [--timeout SECONDS]
+//!     [--sort-inversions|--sort-recipes|--sort-stages] [--fancy] [--json|--plan]`.
+//! -   `<arcosphere> verify [--trace] PATH`.
 //!     where PATH is SOURCE -> TARGET [xCOUNT] [+CATALYSTS] => [IN -> OUT] ((// | '|') [IN -> OUT])*.
-//! -   `<arcosphere> plan PATH`.
+//! -   `<arcosphere> plan [--csv|--fancy] PATH`.
 //!     where PATH is SOURCE -> TARGET [xCOUNT] [+CATALYSTS] => [IN -> OUT] ((// | '|') [IN -> OUT])*.
+//! -   `<arcosphere> explore SOURCE [--depth N]`.
+//! -   `<arcosphere> diff PATH PATH`.
+//!
+//! Each may be preceded by `--family FILE`, to operate on the family of arcospheres defined in FILE.
 
 //  Features
 #![feature(generic_const_exprs)]
@@ -15,26 +20,47 @@
 #![allow(incomplete_features)]
 
 mod command;
+mod family;
+#[cfg(feature = "serde")]
+mod json;
+
+use std::{
+    env,
+    error::Error,
+    fmt::{self, Write},
+    io,
+    str::FromStr,
+    time::Duration,
+};
+
+use arcosphere::{
+    dynamic::DynFamily,
+    executor::DefaultExecutor,
+    model::{ArcosphereFamily, ArcosphereRecipe, ArcosphereSet, SetParseError, StagedPath},
+    planner::Planner,
+    solver::{ResolutionError, Solver, SolverConfiguration},
+    space_exploration::SeArcosphereFamily,
+    verifier::Verifier,
+};
+
+use command::{Command, Format, Options, SolveOptions, SortBy};
 
-use std::{env, error::Error};
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = env::args().skip(1).peekable();
 
-use arcosphere::space_exploration::{SeArcosphereSet, SeStagedPath};
+    let options = Options::parse(&mut args)?;
 
-use command::{Command, SortBy};
+    let Some(family) = options.family else {
+        return run(SeArcosphereFamily, args);
+    };
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let command = command::parse(env::args().skip(1))?;
+    let definition = family::load(&family)?;
 
-    match command {
-        Command::Help => print_help(),
-        Command::Solve {
-            source,
-            target,
-            plan,
-            sort_by,
-        } => run_solve(source, target, plan, sort_by),
-        Command::Verify { path } => run_verify(&path),
-        Command::Plan { path } => run_plan(path),
+    //  The number of arcospheres of a dynamic family is a compile-time parameter, hence only the number of arcospheres
+    //  of Space Exploration is supported, to keep compilation times in check.
+    match definition.arcospheres.len() {
+        8 => run(DynFamily::<8>::install(definition)?, args),
+        n => Err(format!("Unsupported FAMILY {family}: expected 8 arcospheres, got {n}").into()),
     }
 }
 
@@ -42,12 +68,32 @@ fn main() -> Result<(), Box<dyn Error>> {
 //  Implementation
 //
 
+fn run<F, I>(family: F, args: I) -> Result<(), Box<dyn Error>>
+where
+    F: ArcosphereFamily<Arcosphere: Send, Set: Send + FromStr<Err = SetParseError>, Recipe: Send + Sync>
+        + Send
+        + 'static,
+    I: Iterator<Item = String>,
+{
+    let command = command::parse::<F, _>(args)?;
+
+    match command {
+        Command::Help => print_help(),
+        Command::Solve(options) => run_solve(family, options),
+        Command::Verify { path, stdin, trace } => run_verify(family, path.as_ref(), stdin, trace),
+        Command::Plan { path, csv, fancy } => run_plan(family, path, csv, fancy),
+        Command::Explore { source, depth } => run_explore(family, source, depth),
+        Command::Diff { a, b } => run_diff(&a, &b),
+    }
+}
+
 fn print_help() -> Result<(), Box<dyn Error>> {
     const HELP: &str = "
-<arcosphere> [--help] [solve|verify|plan] ARGUMENTS
+<arcosphere> [--help] [--family FAMILY] [solve|verify|plan|explore|diff] ARGUMENTS
 
 Generic options:
 
+-f,--family FAMILY  Use the family of arcospheres & recipes defined in the FAMILY file, instead of Space Exploration.
 -h,--help           Print this help.
 
 
@@ -60,14 +106,24 @@ Solve subcommand:
 SOURCE              The set of source arcospheres.
 TARGET              The set of target arcospheres.
 
+-a,--have HAVE      Use only catalysts drawn from the HAVE set of arcospheres, such as those already in circulation.
+--fancy             Print the arcospheres with their fancy names, such as ε, incompatible with --json.
+-i,--sort-inversions
+                    Sort by number of inversion recipes, from smallest to largest.
+-j,--json           Print the results as a JSON array, incompatible with --plan, requires the serde feature.
+-l,--limit N        Print only the first N results, after sorting.
+-n,--no-catalysts   Print only the results requiring no catalyst, failing if there are none.
 -p,--plan           Execute plan subcommand on each result.
 -r,--sort-recipes   Sort by number of recipes, from smallest to largest.
 -s,--sort-stages    Sort by number of stages, from smallest to largest.
+-t,--timeout SECONDS
+                    Abort the search after SECONDS, printing the results found so far, if any.
 
 
 Verify subcommand:
 
-<arcosphere> verify PATH
+<arcosphere> verify [OPTIONS] PATH
+<arcosphere> verify [OPTIONS] -
 
                     Verifies that the PATH specified is valid. Specifically, verifies that each stage can be executed
                     given the input, and verifies that at the end the expected target (and catalysts) are output.
@@ -75,15 +131,60 @@ Verify subcommand:
 PATH                The path, as output by the solve subcommand. On the command line, quoting is necessary to pass it
                     as a single argument, and avoid the pesky shell from interpreting | or > as special characters.
 
+-,--stdin           Reads the paths from stdin instead, one per line, and prints OK or the error for each.
+
+-t,--trace          Prints the state after applying each stage, up to the first stage which fails to apply.
+
 
 Plan subcommand:
 
-<arcosphere> plan PATH
+<arcosphere> plan [OPTIONS] PATH
 
                     Prints the detailed plan for the given path, if valid.
 
 PATH                The path, as output by the solve subcommand. On the command line, quoting is necessary to pass it
                     as a single argument, and avoid the pesky shell from interpreting | or > as special characters.
+
+-c,--csv            Prints the usage of each recipe as CSV instead: the recipe, the number of times it is used, the
+                    stages in which it is used, its input and its output.
+--fancy             Prints the arcospheres with their fancy names, such as ε, incompatible with --csv.
+
+
+Explore subcommand:
+
+<arcosphere> explore SOURCE [OPTIONS]
+
+                    Prints all the sets reachable from source, including source itself, sorted.
+
+SOURCE              The set of source arcospheres, including any catalysts.
+
+-d,--depth N        Apply at most N recipes, defaults to 20.
+
+
+Diff subcommand:
+
+<arcosphere> diff PATH PATH
+
+                    Prints the recipes and catalysts which differ between the two paths: those only in the first path
+                    prefixed with -, and those only in the second prefixed with +.
+
+PATH                The paths, as output by the solve subcommand. On the command line, quoting is necessary to pass
+                    each as a single argument, and avoid the pesky shell from interpreting | or > as special characters.
+
+
+Family file:
+
+                    A subset of TOML, listing exactly 8 arcospheres then up to 64 recipes, for example:
+
+                    [[arcosphere]]
+                    abbr = \"E\"
+                    full = \"Epsilon\"
+                    fancy = \"ε\"            # Optional.
+                    polarity = \"negative\"  # Or \"positive\".
+
+                    [[recipe]]
+                    input = \"EO\"
+                    output = \"GL\"
 ";
 
     println!("{HELP}");
@@ -91,48 +192,416 @@ PATH                The path, as output by the solve subcommand. On the command
     Ok(())
 }
 
-fn run_solve(
-    source: SeArcosphereSet,
-    target: SeArcosphereSet,
-    plan: bool,
-    sort_by: SortBy,
-) -> Result<(), Box<dyn Error>> {
-    let mut paths = arcosphere::solve(source, target)?;
+fn run_solve<F>(family: F, options: SolveOptions<F>) -> Result<(), Box<dyn Error>>
+where
+    F: ArcosphereFamily<Arcosphere: Send, Set: Send, Recipe: Send + Sync> + Send + 'static,
+{
+    let SolveOptions {
+        source,
+        target,
+        plan,
+        sort_by,
+        format,
+        limit,
+        no_catalysts,
+        timeout,
+        have,
+        fancy,
+    } = options;
+
+    //  Checked upfront, rather than once the search completes.
+    if format == Format::Json && cfg!(not(feature = "serde")) {
+        return Err(JSON_UNSUPPORTED.into());
+    }
+
+    let mut paths = solve(family, source, target, timeout, have)?;
+
+    if no_catalysts {
+        paths.retain(|staged| staged.path.is_catalyst_free());
+
+        if paths.is_empty() {
+            return Err("No catalyst-free path found".into());
+        }
+    }
 
     match sort_by {
-        SortBy::Stages => paths.sort_by_key(|staged| staged.stages.len()),
-        SortBy::Recipes => paths.sort_by_key(|staged| staged.path.recipes.len()),
+        SortBy::Stages => paths.sort_by_key(|staged| staged.stage_count()),
+        SortBy::Recipes => paths.sort_by_key(|staged| staged.recipe_count()),
+        SortBy::Inversions => paths.sort_by_key(|staged| staged.path.inversion_count()),
+    }
+
+    if let Some(limit) = limit {
+        paths.truncate(limit);
+    }
+
+    if format == Format::Json {
+        println!("{}", to_json(&paths)?);
+
+        return Ok(());
     }
 
     if !plan {
         for path in paths {
-            println!("{path}");
+            println!("{}", Styled(&path, fancy));
         }
 
         return Ok(());
     }
 
     for path in paths {
-        println!("{path}");
+        println!("{}", Styled(&path, fancy));
+
+        let plan = Planner::new(family).plan(path)?;
+
+        println!("{}", Styled(&plan, fancy));
+    }
+
+    Ok(())
+}
+
+//  Solves, drawing the catalysts from `have` if any, within a time budget of `timeout` seconds if any.
+fn solve<F>(
+    family: F,
+    source: F::Set,
+    target: F::Set,
+    timeout: Option<u64>,
+    have: Option<F::Set>,
+) -> Result<Vec<StagedPath<F>>, Box<dyn Error>>
+where
+    F: ArcosphereFamily<Arcosphere: Send, Set: Send, Recipe: Send + Sync> + Send + 'static,
+{
+    let configuration = SolverConfiguration {
+        time_budget: timeout.map(Duration::from_secs),
+        ..SolverConfiguration::default()
+    };
+
+    let solver = Solver::<_, DefaultExecutor>::new(family).with_configuration(configuration);
+
+    let mut solutions = match have {
+        Some(have) => solver.solve_iter_with_available_catalysts(source, target, have),
+        None => solver.solve_iter(source, target),
+    };
+
+    let (paths, error) = solutions.best_so_far();
+
+    best_so_far(paths, error)
+}
+
+//  Returns the best paths found, or the error which prevented finding any.
+//
+//  On timeout, the best paths found so far are returned, if any.
+fn best_so_far<F>(
+    paths: Vec<StagedPath<F>>,
+    error: Option<ResolutionError>,
+) -> Result<Vec<StagedPath<F>>, Box<dyn Error>>
+where
+    F: ArcosphereFamily,
+{
+    match error {
+        None => Ok(paths),
+        Some(ResolutionError::Timeout) if paths.is_empty() => Err("Timed out, without finding any path".into()),
+        Some(ResolutionError::Timeout) => {
+            eprintln!("Timed out, printing the paths found so far");
+
+            Ok(paths)
+        }
+        Some(error) => Err(error.into()),
+    }
+}
+
+fn run_verify<F>(family: F, path: Option<&StagedPath<F>>, stdin: bool, trace: bool) -> Result<(), Box<dyn Error>>
+where
+    F: ArcosphereFamily<Set: FromStr<Err = SetParseError>> + 'static,
+{
+    let verifier = Verifier::new(family);
+
+    let verify = |path: &StagedPath<F>| {
+        if !trace {
+            return verifier.verify(path);
+        }
+
+        let (steps, result) = verifier.explain(path);
+
+        for (index, (stage, state)) in steps.into_iter().enumerate() {
+            println!("{:>2}.  {stage}  =>  {state}", index + 1);
+        }
+
+        result
+    };
+
+    if let Some(path) = path {
+        verify(path)?;
+    }
+
+    if !stdin {
+        return Ok(());
+    }
+
+    let mut failures = 0;
+
+    for line in io::stdin().lines() {
+        let line = line?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let result = line
+            .parse::<StagedPath<F>>()
+            .map_err(|e| format!("Failed to parse PATH: {e}"))
+            .and_then(|path| verify(&path).map_err(|e| e.to_string()));
+
+        match result {
+            Ok(()) => println!("OK"),
+            Err(error) => {
+                failures += 1;
+
+                println!("{error}");
+            }
+        }
+    }
 
-        let plan = arcosphere::plan(path)?;
+    if failures > 0 {
+        return Err(format!("{failures} path(s) failed verification").into());
+    }
+
+    Ok(())
+}
+
+fn run_plan<F>(family: F, path: StagedPath<F>, csv: bool, fancy: bool) -> Result<(), Box<dyn Error>>
+where
+    F: ArcosphereFamily + 'static,
+{
+    if csv {
+        Verifier::new(family).verify(&path)?;
 
-        println!("{plan}");
+        print!("{}", to_csv(&path));
+
+        return Ok(());
     }
 
+    let plan = Planner::new(family).plan(path)?;
+
+    print!("{}", Styled(&plan, fancy));
+
     Ok(())
 }
 
-fn run_verify(path: &SeStagedPath) -> Result<(), Box<dyn Error>> {
-    arcosphere::verify(path)?;
+fn run_explore<F>(family: F, source: F::Set, depth: u8) -> Result<(), Box<dyn Error>>
+where
+    F: ArcosphereFamily<Arcosphere: Send, Set: Send, Recipe: Send + Sync> + Send + 'static,
+{
+    let configuration = SolverConfiguration {
+        maximum_recipes: depth,
+        ..SolverConfiguration::default()
+    };
+
+    let solver = Solver::<_, DefaultExecutor>::new(family).with_configuration(configuration);
+
+    let mut reachable: Vec<_> = solver.reachable(source).into_iter().collect();
+
+    reachable.sort_unstable();
+
+    for set in reachable {
+        println!("{set}");
+    }
 
     Ok(())
 }
 
-fn run_plan(path: SeStagedPath) -> Result<(), Box<dyn Error>> {
-    let plan = arcosphere::plan(path)?;
+fn run_diff<F>(a: &StagedPath<F>, b: &StagedPath<F>) -> Result<(), Box<dyn Error>>
+where
+    F: ArcosphereFamily,
+{
+    //  The number of occurrences of each recipe in `a`, minus its number of occurrences in `b`.
+    let mut counts = vec![0isize; F::Recipe::DIMENSION];
+
+    for recipe in &a.path.recipes {
+        counts[recipe.into_index()] += 1;
+    }
+
+    for recipe in &b.path.recipes {
+        counts[recipe.into_index()] -= 1;
+    }
+
+    let (removed, added) = a.path.catalysts.symmetric_difference(&b.path.catalysts);
 
-    print!("{plan}");
+    if counts.iter().all(|count| *count == 0) && removed.is_empty() && added.is_empty() {
+        println!("No difference in recipes or catalysts");
+
+        return Ok(());
+    }
+
+    for recipe in F::Recipe::all().filter(|recipe| counts[recipe.into_index()] > 0) {
+        for _ in 0..counts[recipe.into_index()] {
+            println!("- {recipe}");
+        }
+    }
+
+    for recipe in F::Recipe::all().filter(|recipe| counts[recipe.into_index()] < 0) {
+        for _ in 0..counts[recipe.into_index()].unsigned_abs() {
+            println!("+ {recipe}");
+        }
+    }
+
+    if !removed.is_empty() {
+        println!("- catalysts {removed}");
+    }
+
+    if !added.is_empty() {
+        println!("+ catalysts {added}");
+    }
 
     Ok(())
 }
+
+//  Formats the value with the fancy names of the arcospheres if `fancy`, ie with the alternate flag, and with their
+//  abbreviated names otherwise.
+struct Styled<'a, T>(&'a T, bool);
+
+impl<T> fmt::Display for Styled<'_, T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let Self(value, fancy) = self;
+
+        if *fancy {
+            write!(f, "{value:#}")
+        } else {
+            write!(f, "{value}")
+        }
+    }
+}
+
+//  Formats the usage of each recipe of the path as CSV, in order of first use.
+//
+//  The stages are numbered from 1, as in the plan, and separated by spaces. Sets and recipes are only ever formatted
+//  with the abbreviated names of the arcospheres, which are alphabetic even in a dynamic family, hence no quoting is
+//  necessary.
+fn to_csv<F>(staged: &StagedPath<F>) -> String
+where
+    F: ArcosphereFamily,
+{
+    let mut usages: Vec<(F::Recipe, usize, Vec<usize>)> = Vec::new();
+
+    for (index, recipe) in staged.iter_recipes_with_stage() {
+        let number = index + 1;
+
+        match usages.iter_mut().find(|(r, _, _)| r == recipe) {
+            Some((_, times, stages)) => {
+                *times += 1;
+
+                if stages.last() != Some(&number) {
+                    stages.push(number);
+                }
+            }
+            None => usages.push((*recipe, 1, vec![number])),
+        }
+    }
+
+    let mut csv = String::from("recipe,times,stages,input,output\n");
+
+    for (recipe, times, stages) in usages {
+        let stages: Vec<_> = stages.iter().map(|s| s.to_string()).collect();
+
+        let _ = writeln!(
+            csv,
+            "{recipe},{times},{},{},{}",
+            stages.join(" "),
+            recipe.input(),
+            recipe.output()
+        );
+    }
+
+    csv
+}
+
+//  Formats the paths as a JSON array, one path per line, each path in the `serde` representation of `StagedPath`.
+#[cfg(feature = "serde")]
+fn to_json<F>(paths: &[StagedPath<F>]) -> Result<String, json::Error>
+where
+    F: ArcosphereFamily,
+{
+    let mut output = String::from("[");
+
+    for (i, staged) in paths.iter().enumerate() {
+        let separator = if i > 0 { "," } else { "" };
+
+        output.push_str(separator);
+        output.push_str("\n  ");
+        output.push_str(&json::to_string(staged)?);
+    }
+
+    output.push_str("\n]");
+
+    Ok(output)
+}
+
+#[cfg(not(feature = "serde"))]
+fn to_json<F>(_paths: &[StagedPath<F>]) -> Result<String, Box<dyn Error>>
+where
+    F: ArcosphereFamily,
+{
+    Err(JSON_UNSUPPORTED.into())
+}
+
+const JSON_UNSUPPORTED: &str = "The json option requires the serde feature";
+
+#[cfg(test)]
+mod tests {
+    use arcosphere::space_exploration::SeArcosphereFamily;
+
+    use super::*;
+
+    #[test]
+    fn solve_timeout() {
+        let (source, target) = ("EP".parse().unwrap(), "LX".parse().unwrap());
+
+        let expected = Solver::<_, DefaultExecutor>::new(SeArcosphereFamily)
+            .solve(source, target)
+            .expect("success");
+
+        let paths = solve(SeArcosphereFamily, source, target, Some(3600), None).expect("success");
+
+        assert_eq!(expected, paths);
+
+        //  Timed out before finding anything.
+        let result = solve(SeArcosphereFamily, source, target, Some(0), None);
+
+        assert!(result.is_err());
+
+        let result = solve(SeArcosphereFamily, source, target, Some(0), Some("GO".parse().unwrap()));
+
+        assert!(result.is_err());
+
+        //  Timed out after finding some paths, which are returned.
+        let paths = best_so_far(expected.clone(), Some(ResolutionError::Timeout)).expect("success");
+
+        assert_eq!(expected, paths);
+
+        let result = best_so_far(expected, Some(ResolutionError::Cancelled));
+
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_staged_path() {
+        use arcosphere::space_exploration::SeStagedPath;
+
+        let staged: SeStagedPath = "EP -> TZ x2 + GO  =>  EO -> GL // GP -> OX |  LO -> TX"
+            .parse()
+            .unwrap();
+
+        let expected = concat!(
+            "[\n",
+            r#"  {"path": {"source": "EP", "target": "TZ", "count": 2, "catalysts": "GO", "recipes": ["#,
+            r#"{"input": "EO", "output": "GL"}, {"input": "GP", "output": "OX"}, {"input": "LO", "output": "TX"}]}, "#,
+            r#""stages": [2]}"#,
+            "\n]",
+        );
+
+        assert_eq!(Ok(expected.to_string()), to_json(&[staged]));
+    }
+} // mod tests
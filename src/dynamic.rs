@@ -0,0 +1,390 @@
+//! Dynamic family, defined at runtime.
+//!
+//! A dynamic family is defined from a list of arcospheres -- their names & polarities -- and a list of recipes, rather
+//! than compiled in, for example to load the arcospheres & recipes of a mod from a configuration file.
+//!
+//! The traits of the `model` module are not designed for runtime definitions, hence a few limitations:
+//!
+//! -   The number of arcospheres, `N`, is a compile-time parameter, since `Arcosphere::DIMENSION` is a constant.
+//! -   The number of recipes is at most `MAXIMUM_RECIPES`, the indexes beyond the recipes of the definition mapping to
+//!     no recipe.
+//! -   The names & polarity of an arcosphere, and the recipes, are looked up by index without a family at hand, hence
+//!     the definition is stored in a process-wide registry, and there is at most one dynamic family per process.
+//!
+//! A `DynFamily` carries the installed definition, and is only obtainable by installing it, with `DynFamily::install`.
+//! Prior to the installation, no recipe exists and no arcosphere can be parsed, but querying the names or polarity of
+//! an arcosphere created by index panics.
+
+use core::{error, fmt, hash, ptr, str};
+
+use std::sync::OnceLock;
+
+use crate::model::{
+    Arcosphere, ArcosphereFamily, ArcosphereRecipe, Count, Path, Polarity, RecipeParseError, Set, StagedPath,
+};
+
+/// The maximum number of recipes of a dynamic family.
+pub const MAXIMUM_RECIPES: usize = 64;
+
+/// Set of arcospheres of a dynamic family.
+pub type DynSet<const N: usize> = Set<DynArcosphere<N>>;
+
+/// Path of a dynamic family.
+pub type DynPath<const N: usize> = Path<DynFamily<N>>;
+
+/// StagedPath of a dynamic family.
+pub type DynStagedPath<const N: usize> = StagedPath<DynFamily<N>>;
+
+/// Definition of an arcosphere of a dynamic family.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DynArcosphereDefinition {
+    /// The abbreviated name, eg. 'E'.
+    pub abbr: char,
+    /// The full name, eg. "Epsilon".
+    pub full: String,
+    /// The fancy name, eg. "ε", if any.
+    pub fancy: Option<String>,
+    /// The polarity.
+    pub polarity: Polarity,
+}
+
+impl DynArcosphereDefinition {
+    //  Returns the fancy name, defaulting to the full name.
+    fn fancy(&self) -> &str {
+        self.fancy.as_deref().unwrap_or(&self.full)
+    }
+}
+
+/// Definition of a dynamic family.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DynFamilyDefinition {
+    /// The arcospheres, in order.
+    pub arcospheres: Vec<DynArcosphereDefinition>,
+    /// The recipes, in order, as pairs of input & output, each a string of abbreviated names, eg. ("EO", "GL").
+    pub recipes: Vec<(String, String)>,
+}
+
+/// Error which may occur when installing a dynamic family.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DynFamilyError {
+    /// The number of arcospheres does not match the dimension of the family.
+    DimensionMismatch {
+        /// The dimension of the family.
+        expected: usize,
+        /// The number of arcospheres of the definition.
+        actual: usize,
+    },
+    /// The number of recipes exceeds `MAXIMUM_RECIPES`.
+    TooManyRecipes,
+    /// The abbreviated name of an arcosphere is not alphabetic.
+    ///
+    /// Sets of arcospheres are formatted with their abbreviated names, whose being alphabetic guarantees that they
+    /// neither clash with the syntax of paths nor require quoting or escaping.
+    InvalidAbbreviation(char),
+    /// Two arcospheres share the same abbreviated name.
+    DuplicateArcosphere(char),
+    /// A recipe refers to an unknown arcosphere.
+    UnknownArcosphere(char),
+    /// A recipe does not preserve the number of arcospheres.
+    UnbalancedRecipe {
+        /// Index of the recipe.
+        index: usize,
+    },
+    /// A different dynamic family is already installed.
+    AlreadyInstalled,
+}
+
+impl fmt::Display for DynFamilyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{self:?}")
+    }
+}
+
+impl error::Error for DynFamilyError {}
+
+/// Dynamic family, of `N` arcospheres.
+///
+/// Only obtainable by installing its definition.
+#[derive(Clone, Copy, Debug)]
+pub struct DynFamily<const N: usize>(&'static Registry);
+
+impl<const N: usize> DynFamily<N> {
+    /// Installs the definition of the family, in the process-wide registry.
+    ///
+    /// Installing the same definition multiple times is fine, whereas installing a different definition fails.
+    pub fn install(definition: DynFamilyDefinition) -> Result<Self, DynFamilyError> {
+        let registry = Registry::new(definition, N)?;
+
+        let installed = REGISTRY.get_or_init(|| registry.clone());
+
+        if *installed != registry {
+            return Err(DynFamilyError::AlreadyInstalled);
+        }
+
+        Ok(Self(installed))
+    }
+
+    /// Returns the definition of the family.
+    pub fn definition(&self) -> &'static DynFamilyDefinition {
+        &self.0.definition
+    }
+}
+
+//  There is at most one installed registry, hence comparing their addresses suffices.
+impl<const N: usize> PartialEq for DynFamily<N> {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.0, other.0)
+    }
+}
+
+impl<const N: usize> Eq for DynFamily<N> {}
+
+impl<const N: usize> hash::Hash for DynFamily<N> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: hash::Hasher,
+    {
+        ptr::hash(self.0, state)
+    }
+}
+
+impl<const N: usize> ArcosphereFamily for DynFamily<N>
+where
+    [(); DynArcosphere::<N>::DIMENSION]: Sized,
+{
+    type Arcosphere = DynArcosphere<N>;
+    type Set = DynSet<N>;
+    type Recipe = DynRecipe<N>;
+}
+
+/// Arcosphere of a dynamic family.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct DynArcosphere<const N: usize>(u8);
+
+impl<const N: usize> DynArcosphere<N> {
+    fn definition(&self) -> &'static DynArcosphereDefinition {
+        &Registry::installed(N).definition.arcospheres[self.0 as usize]
+    }
+
+    fn find<P>(predicate: P) -> Option<Self>
+    where
+        P: FnMut(&DynArcosphereDefinition) -> bool,
+    {
+        let index = Registry::get(N)?.definition.arcospheres.iter().position(predicate)?;
+
+        Some(Self(index as u8))
+    }
+}
+
+impl<const N: usize> fmt::Display for DynArcosphere<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        use core::fmt::Write;
+
+        if f.alternate() {
+            return f.write_str(self.fancy());
+        }
+
+        f.write_char(self.abbr())
+    }
+}
+
+impl<const N: usize> Arcosphere for DynArcosphere<N> {
+    const DIMENSION: usize = N;
+
+    fn from_index(index: usize) -> Self {
+        assert!(index < N, "expect index to be in 0..{N}");
+
+        Self(index as u8)
+    }
+
+    fn into_index(self) -> usize {
+        self.0 as usize
+    }
+
+    fn abbr(&self) -> char {
+        self.definition().abbr
+    }
+
+    fn full(&self) -> &'static str {
+        &self.definition().full
+    }
+
+    fn fancy(&self) -> &'static str {
+        self.definition().fancy()
+    }
+
+    fn from_abbr(c: char) -> Option<Self> {
+        Self::find(|sphere| sphere.abbr == c)
+    }
+
+    fn from_fancy(s: &str) -> Option<Self> {
+        Self::find(|sphere| sphere.fancy() == s)
+    }
+
+    fn polarity(&self) -> Polarity {
+        self.definition().polarity
+    }
+}
+
+/// Recipe of a dynamic family.
+///
+/// Unlike the recipes of the space exploration family, a dynamic recipe carries its input & output, so as not to
+/// consult the registry in the midst of a search.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct DynRecipe<const N: usize>
+where
+    [(); DynArcosphere::<N>::DIMENSION]: Sized,
+{
+    index: u8,
+    input: DynSet<N>,
+    output: DynSet<N>,
+}
+
+impl<const N: usize> ArcosphereRecipe for DynRecipe<N>
+where
+    [(); DynArcosphere::<N>::DIMENSION]: Sized,
+{
+    const DIMENSION: usize = MAXIMUM_RECIPES;
+
+    type Arcosphere = DynArcosphere<N>;
+    type Set = DynSet<N>;
+
+    fn from_index(index: usize) -> Self {
+        let recipes = &Registry::installed(N).recipes;
+
+        assert!(index < recipes.len(), "expect index to be in 0..{}", recipes.len());
+
+        let to_set = |counts: &[Count]| {
+            DynSet::<N>::from_counts(counts.iter().enumerate().map(|(i, &c)| (DynArcosphere(i as u8), c)))
+        };
+
+        let (input, output) = &recipes[index];
+        let (input, output) = (to_set(input), to_set(output));
+
+        let index = index as u8;
+
+        Self { index, input, output }
+    }
+
+    fn try_from_index(index: usize) -> Option<Self> {
+        let recipes = Registry::get(N).map_or(0, |registry| registry.recipes.len());
+
+        if index < recipes {
+            Some(Self::from_index(index))
+        } else {
+            None
+        }
+    }
+
+    fn into_index(self) -> usize {
+        self.index as usize
+    }
+
+    fn input(&self) -> Self::Set {
+        self.input
+    }
+
+    fn output(&self) -> Self::Set {
+        self.output
+    }
+}
+
+impl<const N: usize> fmt::Display for DynRecipe<N>
+where
+    [(); DynArcosphere::<N>::DIMENSION]: Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        self.display(f)
+    }
+}
+
+impl<const N: usize> str::FromStr for DynRecipe<N>
+where
+    [(); DynArcosphere::<N>::DIMENSION]: Sized,
+{
+    type Err = RecipeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+//
+//  Implementation
+//
+
+static REGISTRY: OnceLock<Registry> = OnceLock::new();
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Registry {
+    definition: DynFamilyDefinition,
+    //  The input & output of each recipe, as the count of each arcosphere.
+    recipes: Vec<(Vec<Count>, Vec<Count>)>,
+}
+
+impl Registry {
+    //  Returns the registry, if installed for a family of `dimension` arcospheres.
+    fn get(dimension: usize) -> Option<&'static Registry> {
+        REGISTRY
+            .get()
+            .filter(|registry| registry.definition.arcospheres.len() == dimension)
+    }
+
+    fn installed(dimension: usize) -> &'static Registry {
+        Self::get(dimension).expect("dynamic family to be installed")
+    }
+
+    fn new(definition: DynFamilyDefinition, dimension: usize) -> Result<Self, DynFamilyError> {
+        let arcospheres = &definition.arcospheres;
+
+        if arcospheres.len() != dimension {
+            return Err(DynFamilyError::DimensionMismatch {
+                expected: dimension,
+                actual: arcospheres.len(),
+            });
+        }
+
+        if definition.recipes.len() > MAXIMUM_RECIPES {
+            return Err(DynFamilyError::TooManyRecipes);
+        }
+
+        for (i, sphere) in arcospheres.iter().enumerate() {
+            if !sphere.abbr.is_alphabetic() {
+                return Err(DynFamilyError::InvalidAbbreviation(sphere.abbr));
+            }
+
+            if arcospheres[..i].iter().any(|other| other.abbr == sphere.abbr) {
+                return Err(DynFamilyError::DuplicateArcosphere(sphere.abbr));
+            }
+        }
+
+        let to_counts = |spheres: &str| {
+            let mut counts = vec![0; dimension];
+
+            for c in spheres.chars() {
+                let index = arcospheres
+                    .iter()
+                    .position(|sphere| sphere.abbr == c)
+                    .ok_or(DynFamilyError::UnknownArcosphere(c))?;
+
+                counts[index] += 1;
+            }
+
+            Ok(counts)
+        };
+
+        let mut recipes = Vec::with_capacity(definition.recipes.len());
+
+        for (index, (input, output)) in definition.recipes.iter().enumerate() {
+            let (input, output) = (to_counts(input)?, to_counts(output)?);
+
+            if input.iter().sum::<Count>() != output.iter().sum::<Count>() {
+                return Err(DynFamilyError::UnbalancedRecipe { index });
+            }
+
+            recipes.push((input, output));
+        }
+
+        Ok(Self { definition, recipes })
+    }
+}
@@ -19,10 +19,23 @@ pub trait Executor {
         I: IntoIterator<Item = F>,
         F: FnOnce() -> R + Send,
         R: Send;
+
+    /// Executes the two closures provided, possibly in parallel, returning their results.
+    ///
+    /// By default, the closures are executed sequentially, `a` first.
+    fn join<A, B, RA, RB>(a: A, b: B) -> (RA, RB)
+    where
+        A: FnOnce() -> RA + Send,
+        B: FnOnce() -> RB + Send,
+        RA: Send,
+        RB: Send,
+    {
+        (a(), b())
+    }
 }
 
 /// A simple, sequential, executor.
-#[derive(Default)]
+#[derive(Clone, Debug, Default)]
 pub struct SequentialExecutor;
 
 impl Executor for SequentialExecutor {
@@ -41,13 +54,30 @@ pub use rayon::RayonExecutor;
 
 #[cfg(feature = "rayon")]
 mod rayon {
-    use rayon::prelude::*;
+    use std::sync::Arc;
+
+    use rayon::{prelude::*, ThreadPool};
 
     use super::Executor;
 
     /// A simple parallel executor, using the rayon crate.
-    #[derive(Default)]
-    pub struct RayonExecutor;
+    ///
+    /// By default, the global thread pool is used.
+    #[derive(Clone, Debug, Default)]
+    pub struct RayonExecutor {
+        pool: Option<Arc<ThreadPool>>,
+    }
+
+    impl RayonExecutor {
+        /// Creates an executor running its tasks on the given thread pool, rather than the global one.
+        ///
+        /// The pool may be shared between multiple executors.
+        pub fn with_pool(pool: Arc<ThreadPool>) -> Self {
+            let pool = Some(pool);
+
+            Self { pool }
+        }
+    }
 
     impl Executor for RayonExecutor {
         fn execute<I, F, R>(&self, tasks: I) -> impl IntoIterator<Item = R> + use<I, F, R>
@@ -56,15 +86,134 @@ mod rayon {
             F: FnOnce() -> R + Send,
             R: Send,
         {
-            //  FIXME: Is there no way to bridge _without_ allocation?
+            //  The solver passes its tasks as a `Vec`, and collecting a `vec::IntoIter` back into a `Vec` reuses its
+            //  buffer, hence no allocation occurs here.
             //
-            //  (Note that the cost of allocation is likely to matter much for our usecase)
-
+            //  Extending a buffer sized from the size hint of the tasks with `par_extend`, rather than collecting the
+            //  results, performs the same, see the `_extend` benchmarks. Bridging with `par_bridge` would avoid the
+            //  collection for other iterators, but requires a `Send` iterator and loses the ordering of the results.
             let tasks: Vec<_> = tasks.into_iter().collect();
 
-            let results: Vec<_> = tasks.into_par_iter().map(|f| f()).collect();
+            let execute = || -> Vec<_> { tasks.into_par_iter().map(|f| f()).collect() };
+
+            match &self.pool {
+                Some(pool) => pool.install(execute),
+                None => execute(),
+            }
+        }
 
-            results
+        //  The solver only joins from within the tasks it executes, hence on the pool the tasks execute on.
+        fn join<A, B, RA, RB>(a: A, b: B) -> (RA, RB)
+        where
+            A: FnOnce() -> RA + Send,
+            B: FnOnce() -> RB + Send,
+            RA: Send,
+            RB: Send,
+        {
+            rayon::join(a, b)
         }
     }
 } // mod rayon
+
+#[cfg(feature = "tokio")]
+pub use tokio::AsyncExecutor;
+
+#[cfg(feature = "tokio")]
+mod tokio {
+    use tokio::{
+        runtime::{Handle, RuntimeFlavor},
+        task,
+    };
+
+    use super::Executor;
+
+    /// A sequential executor suitable for running within a tokio runtime, without stalling a multi-threaded runtime.
+    ///
+    /// This is an adapter, _not_ a parallel executor: the tasks of a single solve are executed one after the other, as
+    /// with the `SequentialExecutor`.
+    ///
+    /// Ideally, each task would be spawned on the blocking pool via `tokio::task::spawn_blocking`, however the tasks of
+    /// the solver borrow from the solver, whereas `spawn_blocking` requires `'static` tasks.
+    ///
+    /// Instead, the tasks are executed sequentially on the current thread, within `tokio::task::block_in_place` when
+    /// running on a worker thread of a multi-threaded runtime, which hands off the other tasks of the worker, and the
+    /// reactor, to another thread in the meantime. On a current-thread runtime, `block_in_place` is not available, and
+    /// the tasks are executed directly, blocking the runtime.
+    ///
+    /// The tradeoff is thus a lack of parallelism within a single solve. If parallelism is desired, run the whole solve
+    /// with the `RayonExecutor` within `spawn_blocking` instead.
+    #[derive(Clone, Debug, Default)]
+    pub struct AsyncExecutor;
+
+    impl Executor for AsyncExecutor {
+        fn execute<I, F, R>(&self, tasks: I) -> impl IntoIterator<Item = R> + use<I, F, R>
+        where
+            I: IntoIterator<Item = F>,
+            F: FnOnce() -> R + Send,
+            R: Send,
+        {
+            let execute = || -> Vec<R> { tasks.into_iter().map(|f| f()).collect() };
+
+            let in_worker =
+                Handle::try_current().is_ok_and(|handle| handle.runtime_flavor() == RuntimeFlavor::MultiThread);
+
+            if in_worker {
+                task::block_in_place(execute)
+            } else {
+                execute()
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::{sync::mpsc, time::Duration};
+
+        use ::tokio::runtime::Builder;
+
+        use super::*;
+
+        #[test]
+        fn execute_current_thread() {
+            let runtime = Builder::new_current_thread().build().expect("runtime");
+
+            let results: Vec<_> = runtime.block_on(async { execute_squares() });
+
+            assert_eq!(vec![0, 1, 4, 9], results);
+        }
+
+        #[test]
+        fn execute_multi_thread() {
+            let runtime = Builder::new_multi_thread().worker_threads(1).build().expect("runtime");
+
+            let results: Vec<_> = runtime.block_on(async { execute_squares() });
+
+            assert_eq!(vec![0, 1, 4, 9], results);
+
+            //  The tasks executing on the only worker wait on another task, which can only run if the worker is handed
+            //  off to another thread in the meantime.
+            let (sender, receiver) = mpsc::channel();
+
+            let received = runtime.block_on(async move {
+                let executing = ::tokio::spawn(async move {
+                    let tasks = [move || receiver.recv_timeout(Duration::from_secs(10)).is_ok()];
+
+                    AsyncExecutor.execute(tasks).into_iter().collect::<Vec<_>>()
+                });
+
+                ::tokio::spawn(async move { sender.send(()) });
+
+                executing.await.expect("success")
+            });
+
+            assert_eq!(vec![true], received);
+        }
+
+        //  Executes the squares of 0 to 3, as separate tasks.
+        fn execute_squares() -> Vec<u32> {
+            let tasks = (0..4).map(|i| move || i * i);
+
+            AsyncExecutor.execute(tasks).into_iter().collect()
+        }
+    } // mod tests
+} // mod tokio
@@ -11,8 +11,12 @@
 //! If no customization is desired, then just use the default solver:
 //!
 //! ```rust
+//! # #[cfg(feature = "std")]
 //! use arcosphere::solve;
 //!
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() {}
+//! # #[cfg(feature = "std")]
 //! # fn main() -> Result<(), Box<dyn core::error::Error>> {
 //! let source = "EP".parse()?;
 //! let target = "LX".parse()?;
@@ -23,8 +27,13 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! The `std` feature, enabled by default, makes the solver available. Without it, the model, verifier, and planner only
+//! require `core` and `alloc`.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 //  Features
+#![feature(const_ops)]
 #![feature(const_trait_impl)]
 #![feature(generic_const_exprs)]
 #![feature(iter_map_windows)]
@@ -34,9 +43,16 @@
 #![deny(missing_docs)]
 #![allow(incomplete_features)]
 
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod dynamic;
+#[cfg(feature = "std")]
 pub mod executor;
 pub mod model;
 pub mod planner;
+pub mod reversible;
+#[cfg(feature = "std")]
 pub mod solver;
 pub mod space_exploration;
 pub mod verifier;
@@ -44,11 +60,16 @@ pub mod verifier;
 use model::StagedPath;
 
 use planner::{Plan, Planner, PlanningError};
-use solver::{ResolutionError, Solver};
-use space_exploration::{SeArcosphereFamily, SeArcosphereSet};
+use space_exploration::SeArcosphereFamily;
 use verifier::{VerificationError, Verifier};
 
+#[cfg(feature = "std")]
+use solver::{ResolutionError, Solver};
+#[cfg(feature = "std")]
+use space_exploration::SeArcosphereSet;
+
 /// Default Space Exploration solve function.
+#[cfg(feature = "std")]
 pub fn solve(
     input: SeArcosphereSet,
     output: SeArcosphereSet,
@@ -1,8 +1,13 @@
 //! Collection of types & traits used by the solver.
 //!
 //! The `space_exploration` module provides the default arcospheres & recipes normally available in SE.
+//!
+//! Displaying with the alternate flag, ie `{:#}`, names the arcospheres by their fancy names: the sets select them,
+//! and the recipes, paths, staged paths, and plans forward the flag to the sets they display.
+
+use core::{cmp, error, fmt, hash, iter, marker::PhantomData, num::NonZeroU8, ops, str};
 
-use core::{array, cmp, error, fmt, hash, iter, marker::PhantomData, num::NonZeroU8, ops, str};
+use alloc::{format, string::String, vec, vec::Vec};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -15,6 +20,9 @@ pub trait Arcosphere: Copy + fmt::Debug + fmt::Display + Eq + hash::Hash + Ord +
     /// The existing arcospheres are expected to map to indexes `0..Self::DIMENSION`.
     const DIMENSION: usize;
 
+    //  As the trait is `#[const_trait]`, the provided methods may be called in const contexts, where iterators cannot
+    //  be used, hence their manual loops.
+
     /// Returns an array of all arcospheres.
     fn all() -> [Self; Self::DIMENSION] {
         let mut array = [Self::from_index(0); Self::DIMENSION];
@@ -35,6 +43,15 @@ pub trait Arcosphere: Copy + fmt::Debug + fmt::Display + Eq + hash::Hash + Ord +
     /// If `index` lies outside `0..Self::DIMENSION`, the implementation may either panic or return any value.
     fn from_index(index: usize) -> Self;
 
+    /// Creates an arcosphere from an index, or returns `None` if `index` lies outside `0..Self::DIMENSION`.
+    fn try_from_index(index: usize) -> Option<Self> {
+        if index < Self::DIMENSION {
+            Some(Self::from_index(index))
+        } else {
+            None
+        }
+    }
+
     /// Returns the index of an arcosphere.
     ///
     /// The implementation should ensure that the index lies within `0..Self::DIMENSION`, and uniquely identifies the
@@ -51,6 +68,85 @@ pub trait Arcosphere: Copy + fmt::Debug + fmt::Display + Eq + hash::Hash + Ord +
     fn fancy(&self) -> &'static str {
         self.full()
     }
+
+    /// Returns the arcosphere whose abbreviated name is `c`, if any.
+    fn from_abbr(c: char) -> Option<Self> {
+        let mut i = 0;
+
+        while i < Self::DIMENSION {
+            let sphere = Self::from_index(i);
+
+            if sphere.abbr() == c {
+                return Some(sphere);
+            }
+
+            i += 1;
+        }
+
+        None
+    }
+
+    /// Returns the arcosphere whose fancy name is `s`, if any.
+    fn from_fancy(s: &str) -> Option<Self> {
+        let mut i = 0;
+
+        while i < Self::DIMENSION {
+            let sphere = Self::from_index(i);
+
+            if is_same_str(sphere.fancy(), s) {
+                return Some(sphere);
+            }
+
+            i += 1;
+        }
+
+        None
+    }
+
+    /// Returns the polarity of the arcosphere.
+    ///
+    /// Inversion recipes are expected to flip the polarity of their arcospheres, and folding recipes to preserve it.
+    fn polarity(&self) -> Polarity;
+}
+
+//  Compares two strings, in const contexts.
+const fn is_same_str(left: &str, right: &str) -> bool {
+    let (left, right) = (left.as_bytes(), right.as_bytes());
+
+    if left.len() != right.len() {
+        return false;
+    }
+
+    let mut i = 0;
+
+    while i < left.len() {
+        if left[i] != right[i] {
+            return false;
+        }
+
+        i += 1;
+    }
+
+    true
+}
+
+/// The polarity of an arcosphere.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Polarity {
+    /// Positive polarity, counted as +1.
+    Positive,
+    /// Negative polarity, counted as -1.
+    Negative,
+}
+
+impl Polarity {
+    /// Returns the sign of the polarity, either +1 or -1.
+    pub const fn sign(self) -> i16 {
+        match self {
+            Self::Positive => 1,
+            Self::Negative => -1,
+        }
+    }
 }
 
 /// A set of arcospheres.
@@ -98,6 +194,33 @@ pub trait ArcosphereSet:
     /// A set may be neither a subset nor a superset of another.
     fn is_superset_of(&self, other: &Self) -> bool;
 
+    /// Returns whether `self` and `other` are disjoint, that is whether no sphere is contained in both.
+    fn is_disjoint(&self, other: &Self) -> bool;
+
+    /// Returns the intersection of `self` and `other`, that is the minimum count of each sphere.
+    fn intersection(&self, other: &Self) -> Self;
+
+    /// Returns the union of `self` and `other`, that is the maximum count of each sphere.
+    fn union(&self, other: &Self) -> Self;
+
+    /// Returns the symmetric difference of `self` and `other`, as `(self_only, other_only)`.
+    ///
+    /// For each sphere, the count in excess in `self` is part of `self_only`, and the count in excess in `other` is part
+    /// of `other_only`.
+    fn symmetric_difference(&self, other: &Self) -> (Self, Self);
+
+    /// Adds all the elements of `other` to `self`, or returns `None` if one of the counts overflows.
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    /// Removes all the elements of `other` from `self`, or returns `None` if one of the counts underflows.
+    fn checked_sub(self, other: Self) -> Option<Self>;
+
+    /// Multiplies the number of each elements of the set by `n`, or returns `None` if one of the counts overflows.
+    fn checked_mul(self, n: u8) -> Option<Self>;
+
+    /// Multiplies the number of each elements of the set by `n`, saturating each count on overflow.
+    fn saturating_mul(self, n: u8) -> Self;
+
     /// Inserts a sphere in the set.
     fn insert(&mut self, sphere: Self::Arcosphere);
 
@@ -107,15 +230,27 @@ pub trait ArcosphereSet:
     ///
     /// If there is no such sphere in the set.
     fn remove(&mut self, sphere: Self::Arcosphere);
+
+    /// Removes a sphere from the set, or returns an error if there is no such sphere in the set.
+    fn try_remove(&mut self, sphere: Self::Arcosphere) -> Result<(), RemoveError>;
+
+    /// Retains only the spheres for which `keep` returns true, removing all the others.
+    fn retain<P>(&mut self, keep: P)
+    where
+        P: Fn(Self::Arcosphere) -> bool;
+
+    /// Returns the net polarity of the set, that is the number of positive spheres minus the number of negative spheres.
+    fn net_polarity(&self) -> i32;
 }
 
 /// A recipe, transforming a set of arcospheres into another set.
 pub trait ArcosphereRecipe:
     Copy + fmt::Debug + fmt::Display + Eq + hash::Hash + Ord + PartialEq + PartialOrd + str::FromStr
 {
-    /// The total number of arcosphere recipes.
+    /// The total number of indexes of arcosphere recipes.
     ///
-    /// The existing arcosphere recipes are expected to map to indexes `0..Self::DIMENSION`.
+    /// The existing arcosphere recipes are expected to map to indexes `0..Self::DIMENSION`, though not every index need
+    /// map to a recipe, see `try_from_index`.
     const DIMENSION: usize;
 
     /// The type of arcospheres used by the recipe.
@@ -123,11 +258,28 @@ pub trait ArcosphereRecipe:
     /// The type of set of arcospheres used by the recipe.
     type Set: ArcosphereSet<Arcosphere = Self::Arcosphere>;
 
+    /// Returns an iterator over all arcosphere recipes, in order of index.
+    fn all() -> impl Iterator<Item = Self> {
+        (0..Self::DIMENSION).filter_map(Self::try_from_index)
+    }
+
     /// Creates an arcosphere recipe from an index in `0..Self::DIMENSION`.
     ///
-    /// If `index` lies outside `0..Self::DIMENSION`, the implementation may either panic or return any value.
+    /// If `index` does not map to a recipe, the implementation may either panic or return any value.
     fn from_index(index: usize) -> Self;
 
+    /// Creates an arcosphere recipe from an index, or returns `None` if `index` does not map to a recipe.
+    ///
+    /// The default implementation maps every index in `0..Self::DIMENSION` to a recipe, implementations with fewer
+    /// recipes than indexes should override it.
+    fn try_from_index(index: usize) -> Option<Self> {
+        if index < Self::DIMENSION {
+            Some(Self::from_index(index))
+        } else {
+            None
+        }
+    }
+
     /// Returns the index of an arcosphere recipe.
     ///
     /// The implementation should ensure that the index lies within `0..Self::DIMENSION`, and uniquely identifies the
@@ -144,23 +296,43 @@ pub trait ArcosphereRecipe:
     /// The number of the arcospheres in the output MUST match the number of arcospheres in the input.
     fn output(&self) -> Self::Set;
 
+    /// Returns whether the recipe is an inversion, that is whether it changes the net polarity of its arcospheres.
+    fn is_inversion(&self) -> bool {
+        self.input().net_polarity() != self.output().net_polarity()
+    }
+
+    /// Returns whether the recipe is a folding, that is whether it preserves the net polarity of its arcospheres.
+    fn is_folding(&self) -> bool {
+        !self.is_inversion()
+    }
+
     /// Finds the recipe.
     fn find(input: Self::Set, output: Self::Set) -> Result<Self, RecipeIdentifyError> {
-        (0..Self::DIMENSION)
-            .map(|i| Self::from_index(i))
+        Self::all()
             .find(|r| r.input() == input && r.output() == output)
             .ok_or(RecipeIdentifyError::UnknownRecipe)
     }
 
+    /// Returns the input and output of the reversed recipe, that is the output and input of this recipe.
+    fn reversed(&self) -> (Self::Set, Self::Set) {
+        (self.output(), self.input())
+    }
+
+    /// Returns the reverse recipe, transforming the output of this recipe into its input, if any.
+    fn reverse(&self) -> Result<Self, RecipeIdentifyError> {
+        let (input, output) = self.reversed();
+
+        Self::find(input, output)
+    }
+
     /// Parses the recipe, for use in implementing `str::FromStr`.
     fn parse(s: &str) -> Result<Self, RecipeParseError>
     where
         Self::Set: str::FromStr<Err = SetParseError>,
-        [(); Self::DIMENSION]: Sized,
     {
         let mut tokens = s.split_whitespace().peekable();
 
-        let recipe = parse::parse_recipe::<Self, _>(&mut tokens)?;
+        let recipe = parse::parse_recipe::<Self, _>(&mut tokens, &PathGrammar::DEFAULT)?;
 
         if tokens.peek().is_some() {
             return Err(RecipeParseError::Incomplete);
@@ -170,8 +342,12 @@ pub trait ArcosphereRecipe:
     }
 
     /// Formats the recipe, for use in implementing `fmt::Display`.
+    ///
+    /// The alternate flag is forwarded to the sets, see the [module documentation](crate::model).
     fn display(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "{} -> {}", self.input(), self.output())
+        fmt::Display::fmt(&self.input(), f)?;
+        f.write_str(" -> ")?;
+        fmt::Display::fmt(&self.output(), f)
     }
 }
 
@@ -183,8 +359,84 @@ pub trait ArcosphereFamily: Copy + fmt::Debug + Eq + hash::Hash + PartialEq {
     type Set: ArcosphereSet<Arcosphere = Self::Arcosphere>;
     /// The type of recipes.
     type Recipe: ArcosphereRecipe<Arcosphere = Self::Arcosphere, Set = Self::Set>;
+
+    /// Validates that all recipes preserve the number of arcospheres.
+    fn validate() -> Result<(), FamilyError> {
+        for recipe in Self::Recipe::all() {
+            if recipe.input().len() != recipe.output().len() {
+                return Err(FamilyError::UnbalancedRecipe {
+                    index: recipe.into_index(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the effect matrix of the recipes, with a row per arcosphere and a column per recipe, by index.
+    ///
+    /// The entry at `[a][r]` is the number of arcospheres `a` in the output of recipe `r` minus their number in its
+    /// input, hence applying each recipe `r` a number `x[r]` of times changes a set by the product of the matrix by `x`.
+    ///
+    /// #   Panics
+    ///
+    /// If the effect of a recipe on an arcosphere does not fit in an `i8`.
+    fn recipe_matrix() -> Vec<Vec<i8>> {
+        let mut matrix = vec![vec![0i8; Self::Recipe::DIMENSION]; Self::Arcosphere::DIMENSION];
+
+        for recipe in Self::Recipe::all() {
+            let column = recipe.into_index();
+
+            for sphere in recipe.output() {
+                let entry = &mut matrix[sphere.into_index()][column];
+                *entry = entry.strict_add(1);
+            }
+
+            for sphere in recipe.input() {
+                let entry = &mut matrix[sphere.into_index()][column];
+                *entry = entry.strict_sub(1);
+            }
+        }
+
+        matrix
+    }
+}
+
+/// An error which occurs when validating a family.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FamilyError {
+    /// The recipe does not preserve the number of arcospheres.
+    UnbalancedRecipe {
+        /// Index of the recipe.
+        index: usize,
+    },
+}
+
+impl fmt::Display for FamilyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{self:?}")
+    }
+}
+
+impl error::Error for FamilyError {}
+
+/// An error which occurs when composing paths.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CompositionError {
+    /// The target of the first path is not the source of the second path.
+    MismatchedEndpoints,
+    /// The composed count does not fit.
+    CountOverflow,
 }
 
+impl fmt::Display for CompositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{self:?}")
+    }
+}
+
+impl error::Error for CompositionError {}
+
 /// An erorr which occurs when identifying a recipe.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum RecipeIdentifyError {
@@ -243,14 +495,16 @@ impl error::Error for RecipeParseError {}
 ///
 /// This path converts source * count + catalysts into target * count + catalysts.
 #[derive(Clone, Debug)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
 pub struct Path<F>
 where
     F: ArcosphereFamily,
 {
     /// Source arcospheres.
+    #[cfg_attr(feature = "serde", serde(with = "serialization::set"))]
     pub source: F::Set,
     /// Target arcospheres.
+    #[cfg_attr(feature = "serde", serde(with = "serialization::set"))]
     pub target: F::Set,
     /// Minimum number of source -> target transformations to perform.
     ///
@@ -258,8 +512,10 @@ where
     /// up. Thus, while in trivial cases count is 1, with inversions it may be greater.
     pub count: NonZeroU8,
     /// Catalysts to use for this path.
+    #[cfg_attr(feature = "serde", serde(with = "serialization::set"))]
     pub catalysts: F::Set,
     /// Recipes to use, in order.
+    #[cfg_attr(feature = "serde", serde(with = "serialization::recipes"))]
     pub recipes: Vec<F::Recipe>,
 }
 
@@ -267,6 +523,75 @@ impl<F> Path<F>
 where
     F: ArcosphereFamily,
 {
+    /// Returns the number of inversion recipes in the path.
+    pub fn inversion_count(&self) -> usize {
+        self.recipes.iter().filter(|r| r.is_inversion()).count()
+    }
+
+    /// Returns whether the path requires no catalyst.
+    pub fn is_catalyst_free(&self) -> bool {
+        self.catalysts.is_empty()
+    }
+
+    /// Returns the inverse path, transforming target back into source.
+    ///
+    /// The recipes are reversed, and applied in reverse order. Fails if any reversed recipe is not part of the family.
+    pub fn inverse(&self) -> Result<Self, RecipeIdentifyError> {
+        let recipes = self
+            .recipes
+            .iter()
+            .rev()
+            .map(|r| r.reverse())
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            source: self.target,
+            target: self.source,
+            count: self.count,
+            catalysts: self.catalysts,
+            recipes,
+        })
+    }
+
+    /// Composes this path with `other`, transforming the source of this path into the target of `other`.
+    ///
+    /// The target of this path must be the source of `other`. If the counts differ, the composed count is their least
+    /// common multiple, and the recipes of each path are repeated as necessary. The catalysts of either path are
+    /// available throughout, ie the composed catalysts are the union of both.
+    pub fn then(&self, other: &Self) -> Result<Self, CompositionError> {
+        if self.target != other.source {
+            return Err(CompositionError::MismatchedEndpoints);
+        }
+
+        let count = num_integer::lcm(self.count.get() as u16, other.count.get() as u16);
+
+        let count = u8::try_from(count)
+            .ok()
+            .and_then(NonZeroU8::new)
+            .ok_or(CompositionError::CountOverflow)?;
+
+        let first = (count.get() / self.count.get()) as usize;
+        let second = (count.get() / other.count.get()) as usize;
+
+        let mut recipes = Vec::with_capacity(first * self.recipes.len() + second * other.recipes.len());
+
+        for _ in 0..first {
+            recipes.extend_from_slice(&self.recipes);
+        }
+
+        for _ in 0..second {
+            recipes.extend_from_slice(&other.recipes);
+        }
+
+        Ok(Self {
+            source: self.source,
+            target: other.target,
+            count,
+            catalysts: self.catalysts.union(&other.catalysts),
+            recipes,
+        })
+    }
+
     #[allow(clippy::type_complexity)]
     fn tuplify(&self) -> (F::Set, F::Set, NonZeroU8, F::Set, &[F::Recipe]) {
         (self.source, self.target, self.count, self.catalysts, &self.recipes)
@@ -282,20 +607,24 @@ where
     F: ArcosphereFamily,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "{} -> {}", self.source, self.target)?;
+        fmt::Display::fmt(&self.source, f)?;
+        f.write_str(" -> ")?;
+        fmt::Display::fmt(&self.target, f)?;
 
         if self.count.get() > 1 {
             write!(f, " x{}", self.count.get())?;
         }
 
         if !self.catalysts.is_empty() {
-            write!(f, " + {}", self.catalysts)?;
+            f.write_str(" + ")?;
+            fmt::Display::fmt(&self.catalysts, f)?;
         }
 
         for (i, recipe) in self.recipes.iter().enumerate() {
             let separator = if i > 0 { " | " } else { "  =>  " };
 
-            write!(f, "{separator}{recipe}")?;
+            f.write_str(separator)?;
+            fmt::Display::fmt(recipe, f)?;
         }
 
         Ok(())
@@ -305,7 +634,6 @@ where
 impl<F> str::FromStr for Path<F>
 where
     F: ArcosphereFamily<Set: str::FromStr<Err = SetParseError>>,
-    [(); F::Recipe::DIMENSION]: Sized,
 {
     type Err = PathParseError;
 
@@ -314,8 +642,8 @@ where
 
         let mut tokens = s.split_whitespace().peekable();
 
-        let mut this =
-            parse::parse_path_head::<F, _>(&mut tokens).map_err(|error| PathParseError::InvalidHead { error })?;
+        let mut this = parse::parse_path_head::<F, _>(&mut tokens, &PathGrammar::DEFAULT)
+            .map_err(|error| PathParseError::InvalidHead { error })?;
 
         loop {
             let index = this.recipes.len();
@@ -324,7 +652,7 @@ where
                 return Err(PathParseError::UnexpectedSeparator { index });
             }
 
-            let recipe = parse::parse_recipe::<F::Recipe, _>(&mut tokens)
+            let recipe = parse::parse_recipe::<F::Recipe, _>(&mut tokens, &PathGrammar::DEFAULT)
                 .map_err(|error| PathParseError::InvalidRecipe { index, error })?;
 
             this.recipes.push(recipe);
@@ -485,12 +813,30 @@ where
 {
     /// Returns the input of the stage, that is the combined input of all recipes in the stage.
     pub fn input(&self) -> R::Set {
-        self.0.iter().fold(R::Set::default(), |acc, r| acc + r.input())
+        let mut input = R::Set::default();
+
+        self.input_into(&mut input);
+
+        input
     }
 
     /// Returns the output of the stage, that is the combined output of all recipes in the stage.
     pub fn output(&self) -> R::Set {
-        self.0.iter().fold(R::Set::default(), |acc, r| acc + r.output())
+        let mut output = R::Set::default();
+
+        self.output_into(&mut output);
+
+        output
+    }
+
+    /// Adds the input of the stage to `acc`, in place.
+    pub fn input_into(&self, acc: &mut R::Set) {
+        *acc = self.0.iter().fold(*acc, |acc, r| acc + r.input());
+    }
+
+    /// Adds the output of the stage to `acc`, in place.
+    pub fn output_into(&self, acc: &mut R::Set) {
+        *acc = self.0.iter().fold(*acc, |acc, r| acc + r.output());
     }
 }
 
@@ -506,7 +852,8 @@ where
         for (i, recipe) in self.0.iter().enumerate() {
             let separator = if i > 0 { " // " } else { "" };
 
-            write!(f, "{separator}{recipe}")?;
+            f.write_str(separator)?;
+            fmt::Display::fmt(recipe, f)?;
         }
 
         Ok(())
@@ -517,7 +864,11 @@ where
 ///
 /// This path converts source * count + catalysts into target * count + catalysts.
 #[derive(Clone, Debug)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(bound = "", try_from = "serialization::UncheckedStagedPath<F>")
+)]
 pub struct StagedPath<F>
 where
     F: ArcosphereFamily,
@@ -533,6 +884,8 @@ where
     /// recipe in `path.recipes` which starts a stage.
     ///
     /// Since the first recipe always starts a stage, the implicit 0 is omitted.
+    ///
+    /// The boundaries are strictly increasing, within the recipes, hence no stage is empty.
     pub stages: Vec<u8>,
 }
 
@@ -543,8 +896,34 @@ where
     /// Creates a StagedPath, from a Path.
     ///
     /// The staged path thus created is not only guaranteed to be valid, it is also normalized by ensuring that the
-    /// recipes in each stage are sorted.
-    pub fn parallelize(mut path: Path<F>) -> Self {
+    /// recipes in each stage are sorted, and that parallelizing its path anew yields the same staged path.
+    ///
+    /// As a result, paths which only differ by the order of their recipes, and which can be scheduled identically,
+    /// result in identical staged paths.
+    pub fn parallelize(path: Path<F>) -> Self {
+        //  A single pass is greedy, and its result depends on the order of the recipes: a recipe may be scheduled after
+        //  another recipe providing its input, even though said input was already available earlier. Re-parallelizing
+        //  the staged recipes moves such recipes to an earlier stage, and typically converges within a couple passes.
+        //
+        //  The number of passes is bounded nonetheless, just in case.
+        let passes = path.recipes.len();
+
+        let mut staged = Self::parallelize_once(path);
+
+        for _ in 0..passes {
+            let next = Self::parallelize_once(staged.path.clone());
+
+            if next == staged {
+                break;
+            }
+
+            staged = next;
+        }
+
+        staged
+    }
+
+    fn parallelize_once(mut path: Path<F>) -> Self {
         struct Stage<F>
         where
             F: ArcosphereFamily,
@@ -609,9 +988,11 @@ where
             }
         }
 
-        if stages.last().is_some_and(|stage| stage.recipes.is_empty()) {
-            stages.pop();
-        }
+        //  A stage is only pushed once the previous stage receives its first recipe, hence only the last stage is empty.
+        //  Dropping it guarantees that each boundary is strictly after the previous one, and before the end.
+        let last = stages.pop();
+
+        debug_assert!(last.is_some_and(|last| last.recipes.is_empty()));
 
         path.recipes.clear();
 
@@ -633,16 +1014,204 @@ where
         }
     }
 
+    /// Returns the compacted staged path, merging adjacent stages whenever their combined input is available at the
+    /// start of the earlier stage.
+    ///
+    /// The recipes of each merged stage are sorted, as in `parallelize`.
+    pub fn compact(&self) -> Self {
+        let mut available = self.path.source * self.path.count + self.path.catalysts;
+
+        let mut recipes = Vec::with_capacity(self.path.recipes.len());
+        let mut stages = Vec::with_capacity(self.stages.len());
+
+        let mut original = self.stages().peekable();
+
+        while let Some(stage) = original.next() {
+            let start = recipes.len();
+
+            let mut input = stage.input();
+            let mut output = stage.output();
+
+            recipes.extend_from_slice(stage.0);
+
+            while let Some(next) = original.next_if(|next| (input + next.input()).is_subset_of(&available)) {
+                input += next.input();
+                output += next.output();
+
+                recipes.extend_from_slice(next.0);
+            }
+
+            recipes[start..].sort();
+
+            if start > 0 {
+                stages.push(start as u8);
+            }
+
+            available = available - input + output;
+        }
+
+        let path = Path {
+            source: self.path.source,
+            target: self.path.target,
+            count: self.path.count,
+            catalysts: self.path.catalysts,
+            recipes,
+        };
+
+        Self { path, stages }
+    }
+
+    /// Returns the inverse staged path, transforming target back into source.
+    ///
+    /// The stages are applied in reverse order, each with its recipes reversed. Fails if any reversed recipe is not part
+    /// of the family.
+    pub fn inverse(&self) -> Result<Self, RecipeIdentifyError> {
+        let mut recipes = Vec::with_capacity(self.path.recipes.len());
+        let mut stages = Vec::with_capacity(self.stages.len());
+
+        let original: Vec<_> = self.stages().collect();
+
+        for stage in original.into_iter().rev() {
+            if !recipes.is_empty() {
+                stages.push(recipes.len() as u8);
+            }
+
+            let start = recipes.len();
+
+            for recipe in stage.0 {
+                recipes.push(recipe.reverse()?);
+            }
+
+            recipes[start..].sort();
+        }
+
+        let path = Path {
+            source: self.path.target,
+            target: self.path.source,
+            count: self.path.count,
+            catalysts: self.path.catalysts,
+            recipes,
+        };
+
+        Ok(Self { path, stages })
+    }
+
     /// Returns an iterator over the stages.
+    ///
+    /// A path without any recipe has no stage.
     pub fn stages(&self) -> impl Iterator<Item = Stage<'_, F::Recipe>> + use<'_, F> {
+        let recipes = self.path.recipes.len();
+
         let start = iter::once(0);
-        let end = iter::once(self.path.recipes.len());
+        let end = (recipes > 0).then_some(recipes);
 
         let stages = start.chain(self.stages.iter().map(|i| *i as usize)).chain(end);
 
         stages.map_windows(|&[start, end]| Stage(&self.path.recipes[start..end]))
     }
 
+    /// Returns an iterator over the recipes, in order, each with the index of its stage, starting from 0.
+    pub fn iter_recipes_with_stage(&self) -> impl Iterator<Item = (usize, &F::Recipe)> + use<'_, F> {
+        self.stages()
+            .enumerate()
+            .flat_map(|(index, stage)| stage.0.iter().map(move |recipe| (index, recipe)))
+    }
+
+    /// Returns whether the staged path is normalized, that is whether the recipes of each stage are sorted, and the
+    /// boundaries of the stages strictly increasing, within the recipes.
+    ///
+    /// Only the structure of the staged path is checked, not its validity, nor whether `parallelize` would schedule its
+    /// recipes identically.
+    pub fn is_normalized(&self) -> bool {
+        self.is_delimited() && self.stages().all(|stage| stage.0.is_sorted())
+    }
+
+    //  Returns whether the boundaries of the stages are strictly increasing, within the recipes.
+    //
+    //  A path without any recipe is only delimited if it has no stage.
+    fn is_delimited(&self) -> bool {
+        let recipes = self.path.recipes.len();
+
+        if recipes == 0 {
+            return self.stages.is_empty();
+        }
+
+        let start = iter::once(0);
+        let end = iter::once(recipes);
+
+        let boundaries = start.chain(self.stages.iter().map(|i| *i as usize)).chain(end);
+
+        boundaries
+            .map_windows(|&[start, end]| start < end)
+            .all(|delimited| delimited)
+    }
+
+    /// Returns the number of recipes.
+    pub fn recipe_count(&self) -> usize {
+        self.path.recipes.len()
+    }
+
+    /// Returns the number of stages, including the implicit first stage.
+    ///
+    /// As with `stages`, a path without any recipe has no stage.
+    pub fn stage_count(&self) -> usize {
+        self.stages().count()
+    }
+
+    /// Renders the staged path as a Mermaid flowchart.
+    ///
+    /// Each stage is a subgraph, with a node per recipe. Each recipe consumes its input from the previous stage, or the
+    /// source, and produces its output for the next stage, or the target.
+    pub fn to_mermaid(&self) -> String {
+        use fmt::Write;
+
+        let mut mermaid = String::new();
+
+        let source = self.path.source * self.path.count + self.path.catalysts;
+        let target = self.path.target * self.path.count + self.path.catalysts;
+
+        let stages: Vec<_> = self.stages().collect();
+
+        let _ = writeln!(mermaid, "flowchart LR");
+        let _ = writeln!(mermaid, "    source([\"{source}\"])");
+
+        for (i, stage) in stages.iter().enumerate() {
+            let i = i + 1;
+
+            let _ = writeln!(mermaid, "    subgraph stage{i} [\"Stage {i}\"]");
+
+            for (j, recipe) in stage.0.iter().enumerate() {
+                let _ = writeln!(mermaid, "        recipe{i}_{j}[\"{recipe}\"]");
+            }
+
+            let _ = writeln!(mermaid, "    end");
+        }
+
+        let _ = writeln!(mermaid, "    target([\"{target}\"])");
+
+        for (i, stage) in stages.iter().enumerate() {
+            let previous = if i == 0 {
+                String::from("source")
+            } else {
+                format!("stage{i}")
+            };
+            let next = if i + 1 == stages.len() {
+                String::from("target")
+            } else {
+                format!("stage{}", i + 2)
+            };
+
+            for (j, recipe) in stage.0.iter().enumerate() {
+                let current = format!("recipe{}_{j}", i + 1);
+
+                let _ = writeln!(mermaid, "    {previous} -->|\"{}\"| {current}", recipe.input());
+                let _ = writeln!(mermaid, "    {current} -->|\"{}\"| {next}", recipe.output());
+            }
+        }
+
+        mermaid
+    }
+
     #[allow(clippy::type_complexity)]
     fn tuplify(&self) -> (&Path<F>, &[u8]) {
         (&self.path, &self.stages)
@@ -658,55 +1227,95 @@ where
     F: ArcosphereFamily,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "{} -> {}", self.path.source, self.path.target)?;
+        fmt::Display::fmt(&self.path.source, f)?;
+        f.write_str(" -> ")?;
+        fmt::Display::fmt(&self.path.target, f)?;
 
         if self.path.count.get() > 1 {
             write!(f, " x{}", self.path.count.get())?;
         }
 
         if !self.path.catalysts.is_empty() {
-            write!(f, " + {}", self.path.catalysts)?;
+            f.write_str(" + ")?;
+            fmt::Display::fmt(&self.path.catalysts, f)?;
         }
 
+        f.write_str("  =>  ")?;
+
         for (i, stage) in self.stages().enumerate() {
-            let separator = if i > 0 { " |  " } else { "  =>  " };
+            if i > 0 {
+                f.write_str(" |  ")?;
+            }
 
-            write!(f, "{separator}{stage}")?;
+            fmt::Display::fmt(&stage, f)?;
         }
 
         Ok(())
     }
 }
 
-impl<F> str::FromStr for StagedPath<F>
+/// Separators of the textual form of a path.
+///
+/// The separators are tokens of their own, hence must be surrounded by whitespace, and may not contain any.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PathGrammar<'a> {
+    /// The arrow between the source & target of a path, and between the input & output of a recipe, eg. "->".
+    pub arrow: &'a str,
+    /// The arrow between the head of a path and its recipes, eg. "=>".
+    pub recipes: &'a str,
+    /// The separator between recipes of a same stage, eg. "//".
+    pub parallel: &'a str,
+    /// The separator between stages, eg. "|".
+    pub stage: &'a str,
+}
+
+impl PathGrammar<'static> {
+    /// The separators used by `Display` & `FromStr`.
+    pub const DEFAULT: Self = Self {
+        arrow: "->",
+        recipes: "=>",
+        parallel: "//",
+        stage: "|",
+    };
+}
+
+impl Default for PathGrammar<'static> {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl<F> StagedPath<F>
 where
     F: ArcosphereFamily<Set: str::FromStr<Err = SetParseError>>,
-    [(); F::Recipe::DIMENSION]: Sized,
 {
-    type Err = StagedPathParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        const PARALLEL_SEPARATOR: &str = "//";
-        const STAGE_SEPARATOR: &str = "|";
-
+    /// Parses a staged path, using the separators of `grammar`.
+    ///
+    /// `from_str` is equivalent to using the default grammar.
+    pub fn from_str_with(s: &str, grammar: &PathGrammar<'_>) -> Result<Self, StagedPathParseError> {
         let mut tokens = s.split_whitespace().peekable();
 
-        let path =
-            parse::parse_path_head::<F, _>(&mut tokens).map_err(|error| StagedPathParseError::InvalidHead { error })?;
+        let path = parse::parse_path_head::<F, _>(&mut tokens, grammar)
+            .map_err(|error| StagedPathParseError::InvalidHead { error })?;
 
         let mut this = StagedPath { path, stages: vec![] };
 
+        //  A path without any recipe, such as the path from a set to itself, is displayed without any recipe either.
+        if tokens.peek().is_none() {
+            return Ok(this);
+        }
+
         loop {
             let index = this.path.recipes.len();
 
             if tokens
                 .peek()
-                .is_some_and(|s| *s == PARALLEL_SEPARATOR || *s == STAGE_SEPARATOR)
+                .is_some_and(|s| *s == grammar.parallel || *s == grammar.stage)
             {
                 return Err(StagedPathParseError::UnexpectedSeparator { index });
             }
 
-            let recipe = parse::parse_recipe::<F::Recipe, _>(&mut tokens)
+            let recipe = parse::parse_recipe::<F::Recipe, _>(&mut tokens, grammar)
                 .map_err(|error| StagedPathParseError::InvalidRecipe { index, error })?;
 
             this.path.recipes.push(recipe);
@@ -716,11 +1325,11 @@ where
                 break;
             };
 
-            if separator == PARALLEL_SEPARATOR {
+            if separator == grammar.parallel {
                 continue;
             }
 
-            if separator == STAGE_SEPARATOR {
+            if separator == grammar.stage {
                 let index = this
                     .path
                     .recipes
@@ -745,6 +1354,17 @@ where
     }
 }
 
+impl<F> str::FromStr for StagedPath<F>
+where
+    F: ArcosphereFamily<Set: str::FromStr<Err = SetParseError>>,
+{
+    type Err = StagedPathParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_with(s, &PathGrammar::DEFAULT)
+    }
+}
+
 /// Error which may arise when parsing a path.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum StagedPathParseError {
@@ -788,6 +1408,38 @@ impl fmt::Display for StagedPathParseError {
 
 impl error::Error for StagedPathParseError {}
 
+/// Error which may arise when decoding a staged path, from its binary form or through serde.
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum StagedPathDecodeError {
+    /// The bytes ended in the middle of the path.
+    UnexpectedEnd,
+    /// A number is too large for its type.
+    Overflow,
+    /// The count of the path is 0.
+    InvalidCount,
+    /// A recipe index is outside the recipes of the family.
+    InvalidRecipe {
+        /// Index of the invalid recipe.
+        index: usize,
+    },
+    /// The boundaries of the stages are not strictly increasing, within the recipes.
+    InvalidStages,
+    /// There are bytes left after the path.
+    TrailingBytes,
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Display for StagedPathDecodeError {
+    #[cold]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{self:?}")
+    }
+}
+
+#[cfg(feature = "serde")]
+impl error::Error for StagedPathDecodeError {}
+
 //
 //  Identity operations
 //
@@ -837,16 +1489,22 @@ where
     }
 }
 
+/// The number of a given arcosphere in a set.
+///
+/// Wide enough for bulk planning, where a path is multiplied by a large count, at the cost of doubling the size of the
+/// sets compared to `u8`, which the `solve` benchmarks show no measurable slowdown for.
+pub type Count = u16;
+
 /// A set of arcosphere.
 ///
-/// A given arcosphere may appear multiple times in the set.
+/// A given arcosphere may appear multiple times in the set, up to `Count::MAX` times.
 #[derive(Clone, Copy)]
 pub struct Set<A>
 where
     A: Arcosphere,
     [(); A::DIMENSION]: Sized,
 {
-    spheres: [u8; A::DIMENSION],
+    spheres: [Count; A::DIMENSION],
     _marker: PhantomData<A>,
 }
 
@@ -896,6 +1554,46 @@ where
         this
     }
 
+    /// Creates a set of `n` copies of `sphere`.
+    pub const fn splat(sphere: A, n: Count) -> Self
+    where
+        A: ~const Arcosphere,
+    {
+        let mut this = Self::new();
+
+        this.spheres[sphere.into_index()] = n;
+
+        this
+    }
+
+    /// Creates a set from a list of spheres and their counts.
+    ///
+    /// The counts of duplicate spheres are summed.
+    ///
+    /// #   Panics
+    ///
+    /// If one of the counts overflows.
+    pub fn from_counts<I>(counts: I) -> Self
+    where
+        I: IntoIterator<Item = (A, Count)>,
+    {
+        let mut result = Self::new();
+
+        result.extend(counts);
+
+        result
+    }
+
+    /// Creates a set from the count of each sphere, indexed by `Arcosphere::into_index`.
+    pub const fn from_counts_array(counts: [Count; A::DIMENSION]) -> Self {
+        let _marker = PhantomData;
+
+        Self {
+            spheres: counts,
+            _marker,
+        }
+    }
+
     /// Returns where the set contains any arcosphere.
     pub fn is_empty(&self) -> bool {
         self.len() == 0
@@ -913,6 +1611,45 @@ where
         self.spheres[index] > 0
     }
 
+    /// Returns the number of times a sphere is contained in the set.
+    pub fn count_of(&self, sphere: A) -> Count {
+        let index = sphere.into_index();
+
+        self.spheres[index]
+    }
+
+    /// Returns the count of each sphere, indexed by `Arcosphere::into_index`.
+    pub const fn as_counts(&self) -> &[Count; A::DIMENSION] {
+        &self.spheres
+    }
+
+    /// Returns an iterator over the spheres contained in the set, with their count.
+    ///
+    /// Spheres which are not contained in the set are skipped.
+    pub fn iter_counts(&self) -> impl Iterator<Item = (A, Count)> + use<A> {
+        self.spheres
+            .into_iter()
+            .enumerate()
+            .filter(|(_, n)| *n > 0)
+            .map(|(index, n)| (A::from_index(index), n))
+    }
+
+    /// Returns the most common sphere in the set, with its count, or `None` if the set is empty.
+    ///
+    /// In case of ties, the sphere with the lowest index is returned.
+    pub fn most_common(&self) -> Option<(A, Count)> {
+        self.iter_counts()
+            .reduce(|best, candidate| if candidate.1 > best.1 { candidate } else { best })
+    }
+
+    /// Returns the least common sphere contained in the set, with its count, or `None` if the set is empty.
+    ///
+    /// In case of ties, the sphere with the lowest index is returned.
+    pub fn least_common(&self) -> Option<(A, Count)> {
+        self.iter_counts()
+            .reduce(|best, candidate| if candidate.1 < best.1 { candidate } else { best })
+    }
+
     /// Returns whether `self` is a subset of `other`.
     ///
     /// A set may be neither a subset nor a superset of another.
@@ -927,11 +1664,115 @@ where
         other.is_subset_of(self)
     }
 
+    /// Returns whether `self` and `other` are disjoint, that is whether no sphere is contained in both.
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.spheres.iter().zip(&other.spheres).all(|(s, o)| *s == 0 || *o == 0)
+    }
+
+    /// Returns the intersection of `self` and `other`, that is the minimum count of each sphere.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = *self;
+
+        result
+            .spheres
+            .iter_mut()
+            .zip(&other.spheres)
+            .for_each(|(s, o)| *s = cmp::min(*s, *o));
+
+        result
+    }
+
+    /// Returns the union of `self` and `other`, that is the maximum count of each sphere.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = *self;
+
+        result
+            .spheres
+            .iter_mut()
+            .zip(&other.spheres)
+            .for_each(|(s, o)| *s = cmp::max(*s, *o));
+
+        result
+    }
+
+    /// Returns the symmetric difference of `self` and `other`, as `(self_only, other_only)`.
+    ///
+    /// For each sphere, the count in excess in `self` is part of `self_only`, and the count in excess in `other` is part
+    /// of `other_only`.
+    pub fn symmetric_difference(&self, other: &Self) -> (Self, Self) {
+        let mut self_only = *self;
+        let mut other_only = *other;
+
+        for (s, o) in self_only.spheres.iter_mut().zip(&mut other_only.spheres) {
+            let common = cmp::min(*s, *o);
+
+            *s -= common;
+            *o -= common;
+        }
+
+        (self_only, other_only)
+    }
+
+    /// Returns the signed difference of `self` and `other`, that is for each sphere its count in `self` minus its count
+    /// in `other`.
+    ///
+    /// Unlike `-`, which saturates at 0, this preserves the sign of each delta, each delta saturating at the bounds of
+    /// `i16` instead.
+    pub fn signed_difference(&self, other: &Self) -> [i16; A::DIMENSION] {
+        let mut result = [0; A::DIMENSION];
+
+        for ((r, s), o) in result.iter_mut().zip(&self.spheres).zip(&other.spheres) {
+            let delta = i32::from(*s) - i32::from(*o);
+
+            *r = delta.clamp(i16::MIN.into(), i16::MAX.into()) as i16;
+        }
+
+        result
+    }
+
+    /// Adds all the elements of `other` to `self`, or returns `None` if one of the counts overflows.
+    ///
+    /// Unlike `+`, which panics, this detects overflows.
+    pub fn checked_add(mut self, other: Self) -> Option<Self> {
+        for (s, o) in self.spheres.iter_mut().zip(&other.spheres) {
+            *s = s.checked_add(*o)?;
+        }
+
+        Some(self)
+    }
+
+    /// Removes all the elements of `other` from `self`, or returns `None` if one of the counts underflows.
+    ///
+    /// Unlike `-`, which saturates, this detects attempting to remove elements which are not present.
+    pub fn checked_sub(mut self, other: Self) -> Option<Self> {
+        for (s, o) in self.spheres.iter_mut().zip(&other.spheres) {
+            *s = s.checked_sub(*o)?;
+        }
+
+        Some(self)
+    }
+
+    /// Multiplies the number of each elements of the set by `n`, or returns `None` if one of the counts overflows.
+    pub fn checked_mul(mut self, n: u8) -> Option<Self> {
+        for s in &mut self.spheres {
+            *s = s.checked_mul(n.into())?;
+        }
+
+        Some(self)
+    }
+
+    /// Multiplies the number of each elements of the set by `n`, saturating each count on overflow.
+    pub fn saturating_mul(mut self, n: u8) -> Self {
+        self.spheres.iter_mut().for_each(|s| *s = s.saturating_mul(n.into()));
+
+        self
+    }
+
     /// Inserts a sphere in the set.
     ///
     /// #   Panics
     ///
-    /// If there is already 255 such spheres in the set.
+    /// If there is already `Count::MAX` such spheres in the set.
     pub fn insert(&mut self, sphere: A) {
         let index = sphere.into_index();
 
@@ -952,6 +1793,40 @@ where
 
         *n = n.strict_sub(1);
     }
+
+    /// Removes a sphere from the set, or returns an error if there is no such sphere in the set.
+    ///
+    /// Unlike `remove`, never panics, and leaves the set untouched on error.
+    pub fn try_remove(&mut self, sphere: A) -> Result<(), RemoveError> {
+        let index = sphere.into_index();
+
+        let n = &mut self.spheres[index];
+
+        *n = n.checked_sub(1).ok_or(RemoveError::MissingArcosphere)?;
+
+        Ok(())
+    }
+
+    /// Retains only the spheres for which `keep` returns true, removing all the others.
+    pub fn retain<P>(&mut self, keep: P)
+    where
+        P: Fn(A) -> bool,
+    {
+        for (index, n) in self.spheres.iter_mut().enumerate() {
+            if !keep(A::from_index(index)) {
+                *n = 0;
+            }
+        }
+    }
+
+    /// Returns the net polarity of the set, that is the number of positive spheres minus the number of negative spheres.
+    pub fn net_polarity(&self) -> i32 {
+        A::all()
+            .into_iter()
+            .zip(self.spheres)
+            .map(|(sphere, n)| i32::from(sphere.polarity().sign()) * i32::from(n))
+            .sum()
+    }
 }
 
 impl<A> Default for Set<A>
@@ -991,6 +1866,38 @@ where
         self.is_superset_of(other)
     }
 
+    fn is_disjoint(&self, other: &Self) -> bool {
+        self.is_disjoint(other)
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        self.intersection(other)
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        self.union(other)
+    }
+
+    fn symmetric_difference(&self, other: &Self) -> (Self, Self) {
+        self.symmetric_difference(other)
+    }
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        self.checked_add(other)
+    }
+
+    fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_sub(other)
+    }
+
+    fn checked_mul(self, n: u8) -> Option<Self> {
+        self.checked_mul(n)
+    }
+
+    fn saturating_mul(self, n: u8) -> Self {
+        self.saturating_mul(n)
+    }
+
     fn insert(&mut self, sphere: Self::Arcosphere) {
         self.insert(sphere)
     }
@@ -998,6 +1905,21 @@ where
     fn remove(&mut self, sphere: Self::Arcosphere) {
         self.remove(sphere)
     }
+
+    fn try_remove(&mut self, sphere: Self::Arcosphere) -> Result<(), RemoveError> {
+        self.try_remove(sphere)
+    }
+
+    fn retain<P>(&mut self, keep: P)
+    where
+        P: Fn(Self::Arcosphere) -> bool,
+    {
+        self.retain(keep)
+    }
+
+    fn net_polarity(&self) -> i32 {
+        self.net_polarity()
+    }
 }
 
 //
@@ -1022,15 +1944,22 @@ where
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         use fmt::Write;
 
+        //  The alternate flag selects the fancy names, as documented on the module.
+        let fancy = f.alternate();
+
         for (index, n) in self.spheres.iter().enumerate() {
             if *n == 0 {
                 continue;
             }
 
-            let arcosphere = A::from_index(index).abbr();
+            let arcosphere = A::from_index(index);
 
             for _ in 0..*n {
-                f.write_char(arcosphere)?;
+                if fancy {
+                    f.write_str(arcosphere.fancy())?;
+                } else {
+                    f.write_char(arcosphere.abbr())?;
+                }
             }
         }
 
@@ -1046,17 +1975,17 @@ where
     type Err = SetParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mapping: [char; A::DIMENSION] = array::from_fn(|index| A::from_index(index).abbr());
-
         let mut result = Set::new();
 
         for c in s.chars() {
-            let index = mapping
-                .iter()
-                .position(|m| *m == c)
+            //  Only single character fancy names can be recognized.
+            let mut buffer = [0; 4];
+
+            let sphere = A::from_abbr(c)
+                .or_else(|| A::from_fancy(c.encode_utf8(&mut buffer)))
                 .ok_or(SetParseError::UnknownArcosphere(c))?;
 
-            result.insert(A::from_index(index));
+            result.insert(sphere);
         }
 
         Ok(result)
@@ -1078,6 +2007,21 @@ impl fmt::Display for SetParseError {
 
 impl error::Error for SetParseError {}
 
+/// An error which occurs when removing an arcosphere from a set.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RemoveError {
+    /// There is no such arcosphere in the set.
+    MissingArcosphere,
+}
+
+impl fmt::Display for RemoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{self:?}")
+    }
+}
+
+impl error::Error for RemoveError {}
+
 //
 //  Iteration
 //
@@ -1114,6 +2058,36 @@ where
     }
 }
 
+impl<A> iter::Extend<(A, Count)> for Set<A>
+where
+    A: Arcosphere,
+    [(); A::DIMENSION]: Sized,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (A, Count)>,
+    {
+        for (sphere, count) in iter {
+            let n = &mut self.spheres[sphere.into_index()];
+
+            *n = n.strict_add(count);
+        }
+    }
+}
+
+impl<A> iter::FromIterator<(A, Count)> for Set<A>
+where
+    A: Arcosphere,
+    [(); A::DIMENSION]: Sized,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (A, Count)>,
+    {
+        Self::from_counts(iter)
+    }
+}
+
 impl<A> iter::IntoIterator for Set<A>
 where
     A: Arcosphere,
@@ -1135,7 +2109,7 @@ where
     [(); A::DIMENSION]: Sized,
 {
     next: usize,
-    spheres: [u8; A::DIMENSION],
+    spheres: [Count; A::DIMENSION],
     _marker: PhantomData<A>,
 }
 
@@ -1144,7 +2118,7 @@ where
     A: Arcosphere,
     [(); A::DIMENSION]: Sized,
 {
-    fn new(spheres: [u8; A::DIMENSION]) -> Self {
+    fn new(spheres: [Count; A::DIMENSION]) -> Self {
         let next = 0;
         let _marker = PhantomData;
 
@@ -1237,7 +2211,7 @@ where
 //  Set operations
 //
 
-impl<A> ops::AddAssign for Set<A>
+impl<A> const ops::AddAssign for Set<A>
 where
     A: Arcosphere,
     [(); A::DIMENSION]: Sized,
@@ -1248,14 +2222,18 @@ where
     ///
     /// If one of the counts overflows.
     fn add_assign(&mut self, other: Self) {
-        self.spheres
-            .iter_mut()
-            .zip(&other.spheres)
-            .for_each(|(s, o)| *s = s.strict_add(*o));
+        //  Iterators are not usable in const contexts.
+        let mut i = 0;
+
+        while i < A::DIMENSION {
+            self.spheres[i] = self.spheres[i].strict_add(other.spheres[i]);
+
+            i += 1;
+        }
     }
 }
 
-impl<A> ops::Add for Set<A>
+impl<A> const ops::Add for Set<A>
 where
     A: Arcosphere,
     [(); A::DIMENSION]: Sized,
@@ -1308,7 +2286,7 @@ where
     ///
     /// If one of the counts overflows.
     fn mul_assign(&mut self, other: u8) {
-        self.spheres.iter_mut().for_each(|s| *s = s.strict_mul(other));
+        self.spheres.iter_mut().for_each(|s| *s = s.strict_mul(other.into()));
     }
 }
 
@@ -1337,7 +2315,9 @@ where
     ///
     /// If one of the counts overflows.
     fn mul_assign(&mut self, other: NonZeroU8) {
-        self.spheres.iter_mut().for_each(|s| *s = s.strict_mul(other.get()));
+        self.spheres
+            .iter_mut()
+            .for_each(|s| *s = s.strict_mul(other.get().into()));
     }
 }
 
@@ -1355,6 +2335,19 @@ where
     }
 }
 
+impl<A> ops::Index<A> for Set<A>
+where
+    A: Arcosphere,
+    [(); A::DIMENSION]: Sized,
+{
+    type Output = Count;
+
+    /// Returns the number of times `sphere` is contained in the set.
+    fn index(&self, sphere: A) -> &Self::Output {
+        &self.spheres[sphere.into_index()]
+    }
+}
+
 //
 //  Parsing operations
 //
@@ -1365,14 +2358,14 @@ mod parse {
     use super::*;
 
     //  Parses the beginning of the path, stopping short of the recipes.
-    pub(super) fn parse_path_head<'a, F, I>(tokens: &mut Peekable<I>) -> Result<Path<F>, PathHeadParseError>
+    pub(super) fn parse_path_head<'a, F, I>(
+        tokens: &mut Peekable<I>,
+        grammar: &PathGrammar<'_>,
+    ) -> Result<Path<F>, PathHeadParseError>
     where
         F: ArcosphereFamily<Set: FromStr<Err = SetParseError>>,
         I: Iterator<Item = &'a str>,
     {
-        const SINGLE_ARROW: &str = "->";
-        const DOUBLE_ARROW: &str = "=>";
-
         const ONE: NonZeroU8 = NonZeroU8::new(1).unwrap();
 
         let source = tokens
@@ -1382,7 +2375,7 @@ mod parse {
 
         let arrow = tokens.next().ok_or(PathHeadParseError::MissingArrow)?;
 
-        if arrow != SINGLE_ARROW {
+        if arrow != grammar.arrow {
             let error = if arrow.parse::<F::Set>().is_ok() {
                 PathHeadParseError::MissingArrow
             } else {
@@ -1410,7 +2403,7 @@ mod parse {
         let catalysts = if tokens.next_if_eq(&"+").is_some() {
             let catalysts = tokens.next().ok_or(PathHeadParseError::MissingCatalysts)?;
 
-            if catalysts == DOUBLE_ARROW {
+            if catalysts == grammar.recipes {
                 return Err(PathHeadParseError::MissingCatalysts);
             }
 
@@ -1421,7 +2414,7 @@ mod parse {
 
         let arrow = tokens.next().ok_or(PathHeadParseError::MissingEnd)?;
 
-        if arrow != DOUBLE_ARROW {
+        if arrow != grammar.recipes {
             return Err(PathHeadParseError::MissingEnd);
         }
 
@@ -1437,14 +2430,14 @@ mod parse {
     }
 
     //  Parses one recipe.
-    pub(super) fn parse_recipe<'a, R, I>(tokens: &mut Peekable<I>) -> Result<R, RecipeParseError>
+    pub(super) fn parse_recipe<'a, R, I>(
+        tokens: &mut Peekable<I>,
+        grammar: &PathGrammar<'_>,
+    ) -> Result<R, RecipeParseError>
     where
         R: ArcosphereRecipe<Set: FromStr<Err = SetParseError>>,
         I: Iterator<Item = &'a str>,
-        [(); R::DIMENSION]: Sized,
     {
-        const ARROW: &str = "->";
-
         let input = tokens
             .next()
             .ok_or(RecipeParseError::MissingInput)
@@ -1452,7 +2445,7 @@ mod parse {
 
         let arrow = tokens.next().ok_or(RecipeParseError::MissingArrow)?;
 
-        if arrow != ARROW {
+        if arrow != grammar.arrow {
             let error = if arrow.parse::<R::Set>().is_ok() {
                 RecipeParseError::MissingArrow
             } else {
@@ -1481,50 +2474,247 @@ mod serialization {
 
     use serde::{de, ser, Deserialize, Serialize};
 
-    use super::{Arcosphere, Set};
+    use alloc::{vec, vec::Vec};
 
-    impl<A> Serialize for Set<A>
+    use super::{
+        Arcosphere, ArcosphereFamily, ArcosphereRecipe, ArcosphereSet, Count, NonZeroU8, Path, Set, StagedPath,
+        StagedPathDecodeError,
+    };
+
+    //  Compact binary form of a staged path.
+    //
+    //  Each set is encoded as the count of each arcosphere, in order of index, each recipe as its index, and each
+    //  unsigned integer as a LEB128 varint, except for the count and the stages which fit in a single byte.
+    impl<F> StagedPath<F>
     where
-        A: Arcosphere,
-        [(); A::DIMENSION]: Sized,
+        F: ArcosphereFamily,
     {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: ser::Serializer,
-        {
-            //  Let's be reasonable, it's unlikely a set of arcosphere would have over 4096 arcospheres in there.
-            let mut buffer = [0u8; 4096];
-            let mut consumed = 0;
+        /// Encodes the staged path in a compact binary form.
+        ///
+        /// The arcospheres & recipes are encoded by index, hence the binary form can only be decoded by the same family.
+        pub fn to_bytes(&self) -> Vec<u8> {
+            let path = &self.path;
+
+            let mut bytes = Vec::new();
+
+            write_set(&mut bytes, path.source);
+            write_set(&mut bytes, path.target);
+            write_set(&mut bytes, path.catalysts);
+
+            bytes.push(path.count.get());
 
-            for sphere in *self {
-                let written = sphere.abbr().encode_utf8(&mut buffer[consumed..]).len();
+            write_varint(&mut bytes, path.recipes.len() as u64);
 
-                consumed += written;
+            for recipe in &path.recipes {
+                write_varint(&mut bytes, recipe.into_index() as u64);
             }
 
-            let result = core::str::from_utf8(&buffer[..consumed]).expect("valid UTF-8");
+            write_varint(&mut bytes, self.stages.len() as u64);
 
-            serializer.serialize_str(result)
+            bytes.extend_from_slice(&self.stages);
+
+            bytes
         }
-    }
 
-    struct SetVisitor<A>(PhantomData<A>);
+        /// Decodes a staged path from the compact binary form produced by `to_bytes`.
+        pub fn from_bytes(bytes: &[u8]) -> Result<Self, StagedPathDecodeError> {
+            let mut reader = Reader(bytes);
 
-    impl<A> de::Visitor<'_> for SetVisitor<A>
-    where
-        A: Arcosphere,
-        [(); A::DIMENSION]: Sized,
-    {
-        type Value = Set<A>;
+            let source = reader.set()?;
+            let target = reader.set()?;
+            let catalysts = reader.set()?;
 
-        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-            f.write_str("a set of arcospheres")
-        }
+            let count = NonZeroU8::new(reader.byte()?).ok_or(StagedPathDecodeError::InvalidCount)?;
 
-        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
-        where
-            E: de::Error,
-        {
+            let length = reader.length()?;
+
+            //  The length is untrusted, each recipe takes at least a byte.
+            let mut recipes = Vec::with_capacity(length.min(reader.0.len()));
+
+            for index in 0..length {
+                let recipe = usize::try_from(reader.varint()?)
+                    .ok()
+                    .and_then(F::Recipe::try_from_index)
+                    .ok_or(StagedPathDecodeError::InvalidRecipe { index })?;
+
+                recipes.push(recipe);
+            }
+
+            let length = reader.length()?;
+
+            let stages = reader.take(length)?.to_vec();
+
+            if !reader.0.is_empty() {
+                return Err(StagedPathDecodeError::TrailingBytes);
+            }
+
+            let path = Path {
+                source,
+                target,
+                count,
+                catalysts,
+                recipes,
+            };
+
+            UncheckedStagedPath { path, stages }.try_into()
+        }
+    }
+
+    //  A staged path whose stages are not checked yet, as deserialized.
+    #[derive(Deserialize)]
+    #[serde(bound = "")]
+    pub(super) struct UncheckedStagedPath<F>
+    where
+        F: ArcosphereFamily,
+    {
+        pub(super) path: Path<F>,
+        pub(super) stages: Vec<u8>,
+    }
+
+    impl<F> TryFrom<UncheckedStagedPath<F>> for StagedPath<F>
+    where
+        F: ArcosphereFamily,
+    {
+        type Error = StagedPathDecodeError;
+
+        fn try_from(unchecked: UncheckedStagedPath<F>) -> Result<Self, Self::Error> {
+            let UncheckedStagedPath { path, stages } = unchecked;
+
+            let staged = Self { path, stages };
+
+            //  The stages are untrusted, and out of bounds stages would make `stages` panic.
+            if !staged.is_delimited() {
+                return Err(StagedPathDecodeError::InvalidStages);
+            }
+
+            Ok(staged)
+        }
+    }
+
+    fn write_set<S>(bytes: &mut Vec<u8>, set: S)
+    where
+        S: ArcosphereSet,
+    {
+        let mut counts = vec![0u64; S::Arcosphere::DIMENSION];
+
+        for sphere in set {
+            counts[sphere.into_index()] += 1;
+        }
+
+        for count in counts {
+            write_varint(bytes, count);
+        }
+    }
+
+    fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+        while value >= 0x80 {
+            bytes.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+
+        bytes.push(value as u8);
+    }
+
+    //  Reads the compact binary form, front to back.
+    struct Reader<'a>(&'a [u8]);
+
+    impl Reader<'_> {
+        fn byte(&mut self) -> Result<u8, StagedPathDecodeError> {
+            let (first, rest) = self.0.split_first().ok_or(StagedPathDecodeError::UnexpectedEnd)?;
+
+            self.0 = rest;
+
+            Ok(*first)
+        }
+
+        fn take(&mut self, length: usize) -> Result<&[u8], StagedPathDecodeError> {
+            if length > self.0.len() {
+                return Err(StagedPathDecodeError::UnexpectedEnd);
+            }
+
+            let (taken, rest) = self.0.split_at(length);
+
+            self.0 = rest;
+
+            Ok(taken)
+        }
+
+        fn varint(&mut self) -> Result<u64, StagedPathDecodeError> {
+            let mut value = 0u64;
+
+            for shift in (0..64).step_by(7) {
+                let byte = self.byte()?;
+                let low = u64::from(byte & 0x7F);
+
+                if low.leading_zeros() < shift {
+                    return Err(StagedPathDecodeError::Overflow);
+                }
+
+                value |= low << shift;
+
+                if byte & 0x80 == 0 {
+                    return Ok(value);
+                }
+            }
+
+            Err(StagedPathDecodeError::Overflow)
+        }
+
+        fn length(&mut self) -> Result<usize, StagedPathDecodeError> {
+            usize::try_from(self.varint()?).map_err(|_| StagedPathDecodeError::Overflow)
+        }
+
+        fn set<S>(&mut self) -> Result<S, StagedPathDecodeError>
+        where
+            S: ArcosphereSet,
+        {
+            let mut set = S::default();
+
+            for index in 0..S::Arcosphere::DIMENSION {
+                let count: Count = self.varint()?.try_into().map_err(|_| StagedPathDecodeError::Overflow)?;
+
+                let sphere = S::Arcosphere::from_index(index);
+
+                for _ in 0..count {
+                    set.insert(sphere);
+                }
+            }
+
+            Ok(set)
+        }
+    }
+
+    impl<A> Serialize for Set<A>
+    where
+        A: Arcosphere,
+        [(); A::DIMENSION]: Sized,
+    {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            //  With up to `Count::MAX` of each arcosphere, a fixed-size buffer is no longer reasonable.
+            serializer.collect_str(self)
+        }
+    }
+
+    struct SetVisitor<A>(PhantomData<A>);
+
+    impl<A> de::Visitor<'_> for SetVisitor<A>
+    where
+        A: Arcosphere,
+        [(); A::DIMENSION]: Sized,
+    {
+        type Value = Set<A>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+            f.write_str("a set of arcospheres")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
             value.parse().map_err(E::custom)
         }
     }
@@ -1541,4 +2731,882 @@ mod serialization {
             deserializer.deserialize_any(SetVisitor(PhantomData))
         }
     }
+
+    //  Serializes any set of arcospheres as a string, for use with `#[serde(with = "...")]`.
+    pub(super) mod set {
+        use alloc::string::String;
+
+        use serde::{de, ser, Deserialize};
+
+        use crate::model::ArcosphereSet;
+
+        pub(crate) fn serialize<T, S>(set: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            T: ArcosphereSet,
+            S: ser::Serializer,
+        {
+            serializer.collect_str(set)
+        }
+
+        pub(crate) fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+        where
+            T: ArcosphereSet,
+            D: de::Deserializer<'de>,
+        {
+            let value = String::deserialize(deserializer)?;
+
+            value
+                .parse()
+                .map_err(|_| de::Error::invalid_value(de::Unexpected::Str(&value), &"a set of arcospheres"))
+        }
+    } // mod set
+
+    //  Serializes recipes by their input & output, for use with `#[serde(with = "...")]`.
+    //
+    //  Unlike the index of a recipe, its input & output are self-describing, and survive re-ordering of the recipes.
+    pub(super) mod recipes {
+        use alloc::{
+            string::{String, ToString},
+            vec::Vec,
+        };
+
+        use serde::{de, ser, Deserialize, Serialize};
+
+        use crate::model::ArcosphereRecipe;
+
+        #[derive(Deserialize, Serialize)]
+        struct NamedRecipe {
+            input: String,
+            output: String,
+        }
+
+        pub(crate) fn serialize<R, S>(recipes: &[R], serializer: S) -> Result<S::Ok, S::Error>
+        where
+            R: ArcosphereRecipe,
+            S: ser::Serializer,
+        {
+            let recipes = recipes.iter().map(|recipe| NamedRecipe {
+                input: recipe.input().to_string(),
+                output: recipe.output().to_string(),
+            });
+
+            serializer.collect_seq(recipes)
+        }
+
+        pub(crate) fn deserialize<'de, R, D>(deserializer: D) -> Result<Vec<R>, D::Error>
+        where
+            R: ArcosphereRecipe,
+            D: de::Deserializer<'de>,
+        {
+            fn parse<R, E>(value: &str) -> Result<R::Set, E>
+            where
+                R: ArcosphereRecipe,
+                E: de::Error,
+            {
+                value
+                    .parse()
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &"a set of arcospheres"))
+            }
+
+            let recipes = Vec::<NamedRecipe>::deserialize(deserializer)?;
+
+            recipes
+                .into_iter()
+                .map(|recipe| {
+                    let input = parse::<R, D::Error>(&recipe.input)?;
+                    let output = parse::<R, D::Error>(&recipe.output)?;
+
+                    R::find(input, output).map_err(de::Error::custom)
+                })
+                .collect()
+        }
+    } // mod recipes
 } // mod serialization
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::string::ToString;
+
+    use std::collections::HashSet;
+
+    use crate::{
+        space_exploration::{SeArcosphere, SeArcosphereFamily, SeArcosphereRecipe, SeArcosphereSet, SeStagedPath},
+        verifier::Verifier,
+    };
+
+    use super::*;
+
+    const ONE: NonZeroU8 = NonZeroU8::new(1).unwrap();
+
+    #[test]
+    fn family_validate() {
+        assert_eq!(Ok(()), SeArcosphereFamily::validate());
+
+        //  The SE recipes, except for the second, whose input is missing a sphere.
+        #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        struct UnbalancedRecipe(SeArcosphereRecipe);
+
+        impl ArcosphereRecipe for UnbalancedRecipe {
+            const DIMENSION: usize = SeArcosphereRecipe::DIMENSION;
+
+            type Arcosphere = SeArcosphere;
+            type Set = SeArcosphereSet;
+
+            fn from_index(index: usize) -> Self {
+                Self(SeArcosphereRecipe::from_index(index))
+            }
+
+            fn into_index(self) -> usize {
+                self.0.into_index()
+            }
+
+            fn input(&self) -> Self::Set {
+                let mut input = self.0.input();
+
+                if self.into_index() == 1 {
+                    input.remove(input.into_iter().next().expect("non-empty input"));
+                }
+
+                input
+            }
+
+            fn output(&self) -> Self::Set {
+                self.0.output()
+            }
+        }
+
+        impl fmt::Display for UnbalancedRecipe {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+                self.display(f)
+            }
+        }
+
+        impl str::FromStr for UnbalancedRecipe {
+            type Err = RecipeParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::parse(s)
+            }
+        }
+
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+        struct UnbalancedFamily;
+
+        impl ArcosphereFamily for UnbalancedFamily {
+            type Arcosphere = SeArcosphere;
+            type Set = SeArcosphereSet;
+            type Recipe = UnbalancedRecipe;
+        }
+
+        assert_eq!(
+            Err(FamilyError::UnbalancedRecipe { index: 1 }),
+            UnbalancedFamily::validate()
+        );
+    }
+
+    #[test]
+    fn arcosphere_from_names() {
+        assert_eq!(Some(SeArcosphere::Epsilon), SeArcosphere::from_abbr('E'));
+        assert_eq!(None, SeArcosphere::from_abbr('ε'));
+
+        assert_eq!(Some(SeArcosphere::Zeta), SeArcosphere::from_fancy("ζ"));
+        assert_eq!(None, SeArcosphere::from_fancy("Z"));
+    }
+
+    #[test]
+    fn set_most_least_common() {
+        let set: SeArcosphereSet = "EGGLLOOO".parse().unwrap();
+
+        assert_eq!(Some((SeArcosphere::Omega, 3)), set.most_common());
+        assert_eq!(Some((SeArcosphere::Epsilon, 1)), set.least_common());
+
+        let set: SeArcosphereSet = "GGLLX".parse().unwrap();
+
+        assert_eq!(Some((SeArcosphere::Gamma, 2)), set.most_common());
+        assert_eq!(Some((SeArcosphere::Xi, 1)), set.least_common());
+
+        assert_eq!(None, SeArcosphereSet::new().most_common());
+        assert_eq!(None, SeArcosphereSet::new().least_common());
+    }
+
+    #[test]
+    fn set_const_add() {
+        const EO: SeArcosphereSet = Set::from_spheres([SeArcosphere::Epsilon, SeArcosphere::Omega]);
+        const PG: SeArcosphereSet = Set::from_spheres([SeArcosphere::Phi, SeArcosphere::Gamma]);
+
+        const EGOP: SeArcosphereSet = EO + PG;
+
+        const TWICE: SeArcosphereSet = {
+            let mut twice = EO;
+            twice += EO;
+            twice
+        };
+
+        assert_eq!("EGOP".parse::<SeArcosphereSet>().unwrap(), EGOP);
+        assert_eq!("EEOO".parse::<SeArcosphereSet>().unwrap(), TWICE);
+    }
+
+    #[test]
+    fn set_index() {
+        let set: SeArcosphereSet = "EEOP".parse().unwrap();
+
+        assert_eq!(2, set[SeArcosphere::Epsilon]);
+        assert_eq!(1, set[SeArcosphere::Omega]);
+        assert_eq!(0, set[SeArcosphere::Gamma]);
+
+        for sphere in SeArcosphere::all() {
+            assert_eq!(set.count_of(sphere), set[sphere]);
+        }
+    }
+
+    #[test]
+    fn set_counts_array() {
+        let set: SeArcosphereSet = "EEOP".parse().unwrap();
+        let counts = *set.as_counts();
+
+        assert_eq!(2, counts[SeArcosphere::Epsilon.into_index()]);
+        assert_eq!(4, counts.iter().sum::<u16>());
+        assert_eq!(set, SeArcosphereSet::from_counts_array(counts));
+    }
+
+    #[test]
+    fn set_is_disjoint() {
+        let set: SeArcosphereSet = "EEOP".parse().unwrap();
+
+        assert!(set.is_disjoint(&"GGLX".parse().unwrap()));
+        assert!(set.is_disjoint(&SeArcosphereSet::new()));
+        assert!(!set.is_disjoint(&"GP".parse().unwrap()));
+        assert!(!set.is_disjoint(&set));
+    }
+
+    #[test]
+    fn set_iter_counts() {
+        use SeArcosphere::*;
+
+        let set: SeArcosphereSet = "EEGPP".parse().unwrap();
+
+        let counts: Vec<_> = set.iter_counts().collect();
+
+        assert_eq!(vec![(Epsilon, 2), (Gamma, 1), (Phi, 2)], counts);
+        assert_eq!(None, SeArcosphereSet::new().iter_counts().next());
+    }
+
+    #[test]
+    fn set_intersection_union() {
+        let a: SeArcosphereSet = "EEGP".parse().unwrap();
+        let b: SeArcosphereSet = "EGGL".parse().unwrap();
+
+        assert_eq!("EG", a.intersection(&b).to_string());
+        assert_eq!("EEGGLP", a.union(&b).to_string());
+
+        assert_eq!(a.intersection(&b), b.intersection(&a));
+        assert_eq!(a.union(&b), b.union(&a));
+
+        let empty = SeArcosphereSet::new();
+
+        assert_eq!(empty, a.intersection(&empty));
+        assert_eq!(a, a.union(&empty));
+    }
+
+    #[test]
+    fn set_symmetric_difference() {
+        let a: SeArcosphereSet = "EEGP".parse().unwrap();
+        let b: SeArcosphereSet = "EGGL".parse().unwrap();
+
+        let (a_only, b_only) = a.symmetric_difference(&b);
+
+        assert_eq!("EP", a_only.to_string());
+        assert_eq!("GL", b_only.to_string());
+
+        let empty = SeArcosphereSet::new();
+
+        assert_eq!((empty, empty), a.symmetric_difference(&a));
+        assert_eq!((a, empty), a.symmetric_difference(&empty));
+    }
+
+    #[test]
+    fn set_checked_saturating_mul() {
+        use SeArcosphere::*;
+
+        let set: SeArcosphereSet = "EG".parse().unwrap();
+
+        assert_eq!(Some("EEEGGG".parse().unwrap()), set.checked_mul(3));
+        assert_eq!(Some(SeArcosphereSet::new()), set.checked_mul(0));
+        assert_eq!(set * NonZeroU8::new(3).unwrap(), set.saturating_mul(3));
+
+        //  A single count overflowing is enough.
+        let large = set + SeArcosphereSet::splat(Gamma, Count::MAX / 2 + 1);
+
+        assert_eq!(None, large.checked_mul(2));
+
+        let saturated = large.saturating_mul(2);
+
+        assert_eq!(2, saturated.as_counts()[Epsilon.into_index()]);
+        assert_eq!(Count::MAX, saturated.as_counts()[Gamma.into_index()]);
+    }
+
+    #[test]
+    fn set_splat() {
+        use SeArcosphere::*;
+
+        const GAMMAS: SeArcosphereSet = SeArcosphereSet::splat(Gamma, 4);
+
+        assert_eq!("GGGG", GAMMAS.to_string());
+        assert_eq!(SeArcosphereSet::new(), SeArcosphereSet::splat(Gamma, 0));
+        assert_eq!("EEGGGG", (SeArcosphereSet::splat(Epsilon, 2) + GAMMAS).to_string());
+
+        //  Counts are not limited to a byte.
+        assert_eq!(300, SeArcosphereSet::splat(Gamma, 300).len());
+    }
+
+    #[test]
+    fn set_signed_difference() {
+        let source: SeArcosphereSet = "EEOP".parse().unwrap();
+        let target: SeArcosphereSet = "EGOO".parse().unwrap();
+
+        let delta = target.signed_difference(&source);
+
+        assert_eq!(-1, delta[SeArcosphere::Epsilon.into_index()]);
+        assert_eq!(1, delta[SeArcosphere::Gamma.into_index()]);
+        assert_eq!(1, delta[SeArcosphere::Omega.into_index()]);
+        assert_eq!(-1, delta[SeArcosphere::Phi.into_index()]);
+        assert_eq!(0, delta.iter().sum::<i16>());
+
+        assert_eq!([0; 8], source.signed_difference(&source));
+    }
+
+    #[test]
+    fn set_checked_sub() {
+        let set: SeArcosphereSet = "EEOP".parse().unwrap();
+
+        assert_eq!(Some("EP".parse().unwrap()), set.checked_sub("EO".parse().unwrap()));
+        assert_eq!(Some(SeArcosphereSet::new()), set.checked_sub(set));
+        assert_eq!(None, set.checked_sub("EEE".parse().unwrap()));
+        assert_eq!(None, set.checked_sub("G".parse().unwrap()));
+    }
+
+    #[test]
+    fn set_try_remove() {
+        let mut set: SeArcosphereSet = "EEO".parse().unwrap();
+
+        let epsilon = SeArcosphere::Epsilon;
+
+        assert_eq!(Ok(()), set.try_remove(epsilon));
+        assert_eq!(Ok(()), set.try_remove(epsilon));
+        assert_eq!(Err(RemoveError::MissingArcosphere), set.try_remove(epsilon));
+
+        assert_eq!("O".parse::<SeArcosphereSet>().unwrap(), set);
+    }
+
+    #[test]
+    fn set_retain() {
+        let mut set: SeArcosphereSet = "EGGLOOPZ".parse().unwrap();
+
+        set.retain(|sphere| sphere.polarity() == Polarity::Positive);
+
+        assert_eq!("GGOOZ", set.to_string());
+
+        set.retain(|_| false);
+
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn recipe_is_inversion() {
+        assert!(SeArcosphereRecipe::ELPX.is_inversion());
+        assert!(SeArcosphereRecipe::GOTZ.is_inversion());
+
+        assert!(SeArcosphereRecipe::LO.is_folding());
+        assert!(SeArcosphereRecipe::XG.is_folding());
+    }
+
+    #[test]
+    fn recipe_all() {
+        let indexes: Vec<_> = SeArcosphereRecipe::all().map(SeArcosphereRecipe::into_index).collect();
+
+        let expected: Vec<_> = (0..SeArcosphereRecipe::DIMENSION).collect();
+
+        assert_eq!(expected, indexes);
+    }
+
+    #[test]
+    fn recipe_matrix() {
+        let matrix = SeArcosphereFamily::recipe_matrix();
+
+        assert_eq!(SeArcosphere::DIMENSION, matrix.len());
+
+        for recipe in SeArcosphereRecipe::all() {
+            let column: Vec<_> = matrix.iter().map(|row| row[recipe.into_index()]).collect();
+
+            //  Recipes preserve the number of arcospheres.
+            assert_eq!(0, column.iter().map(|e| *e as i32).sum::<i32>(), "{recipe}");
+        }
+
+        let eo = SeArcosphereRecipe::EO.into_index();
+
+        for (sphere, effect) in [
+            (SeArcosphere::Epsilon, -1),
+            (SeArcosphere::Omega, -1),
+            (SeArcosphere::Gamma, 1),
+            (SeArcosphere::Lambda, 1),
+            (SeArcosphere::Phi, 0),
+        ] {
+            assert_eq!(effect, matrix[sphere.into_index()][eo], "{sphere}");
+        }
+    }
+
+    #[test]
+    fn try_from_index() {
+        assert_eq!(Some(SeArcosphere::from_index(0)), SeArcosphere::try_from_index(0));
+        assert_eq!(None, SeArcosphere::try_from_index(SeArcosphere::DIMENSION));
+
+        let last = SeArcosphereRecipe::DIMENSION - 1;
+
+        assert_eq!(
+            Some(SeArcosphereRecipe::from_index(last)),
+            SeArcosphereRecipe::try_from_index(last)
+        );
+        assert_eq!(None, SeArcosphereRecipe::try_from_index(SeArcosphereRecipe::DIMENSION));
+    }
+
+    #[test]
+    fn parallelize_canonical() {
+        const TWO: NonZeroU8 = NonZeroU8::new(2).unwrap();
+
+        let lo = SeArcosphereRecipe::LO;
+        let lt = SeArcosphereRecipe::LT;
+        let pz = SeArcosphereRecipe::PZ;
+        let xg = SeArcosphereRecipe::XG;
+        let xz = SeArcosphereRecipe::XZ;
+
+        let path = |recipes| Path {
+            source: "LO".parse().unwrap(),
+            target: "ET".parse().unwrap(),
+            count: TWO,
+            catalysts: "GX".parse().unwrap(),
+            recipes,
+        };
+
+        //  In the first, XZ consumes the Z produced by LT, whereas in the second it consumes the Z produced by XG.
+        let first = SeStagedPath::parallelize(path(vec![lo, lt, xz, xg, lo, pz]));
+        let second = SeStagedPath::parallelize(path(vec![lo, lo, xg, lt, xz, pz]));
+
+        assert_eq!(vec![3, 5], first.stages);
+        assert_eq!(first, second);
+        assert_eq!(first, SeStagedPath::parallelize(first.path.clone()));
+
+        let set: HashSet<_> = [first, second].into_iter().collect();
+
+        assert_eq!(1, set.len());
+    }
+
+    #[test]
+    fn parallelize_no_empty_stage() {
+        //  XG requires the G produced by EO, hence lands alone in a freshly pushed stage.
+        let path = Path {
+            source: "EOX".parse().unwrap(),
+            target: "LLZ".parse().unwrap(),
+            count: ONE,
+            catalysts: SeArcosphereSet::new(),
+            recipes: vec![SeArcosphereRecipe::EO, SeArcosphereRecipe::XG],
+        };
+
+        let staged = SeStagedPath::parallelize(path.clone());
+
+        assert_eq!(vec![1], staged.stages);
+        assert_eq!(2, staged.stages().count());
+        assert!(staged.stages().all(|stage| !stage.0.is_empty()));
+
+        //  Duplicate boundaries are not hidden by `stages`, and are flagged instead.
+        let staged = SeStagedPath {
+            path,
+            stages: vec![1, 1],
+        };
+
+        assert_eq!(3, staged.stages().count());
+        assert!(!staged.is_normalized());
+    }
+
+    #[test]
+    fn staged_path_to_mermaid() {
+        let path = "LO -> ET x2 + GX  =>  LO -> TX // LO -> TX // GX -> LZ |  LT -> EZ // XZ -> PT |  PZ -> EG";
+        let path: SeStagedPath = path.parse().unwrap();
+
+        let expected = r#"flowchart LR
+    source(["GLLOOX"])
+    subgraph stage1 ["Stage 1"]
+        recipe1_0["LO -> TX"]
+        recipe1_1["LO -> TX"]
+        recipe1_2["GX -> LZ"]
+    end
+    subgraph stage2 ["Stage 2"]
+        recipe2_0["LT -> EZ"]
+        recipe2_1["XZ -> PT"]
+    end
+    subgraph stage3 ["Stage 3"]
+        recipe3_0["PZ -> EG"]
+    end
+    target(["EEGTTX"])
+    source -->|"LO"| recipe1_0
+    recipe1_0 -->|"TX"| stage2
+    source -->|"LO"| recipe1_1
+    recipe1_1 -->|"TX"| stage2
+    source -->|"GX"| recipe1_2
+    recipe1_2 -->|"LZ"| stage2
+    stage1 -->|"LT"| recipe2_0
+    recipe2_0 -->|"EZ"| stage3
+    stage1 -->|"XZ"| recipe2_1
+    recipe2_1 -->|"PT"| stage3
+    stage2 -->|"PZ"| recipe3_0
+    recipe3_0 -->|"EG"| target
+"#;
+
+        assert_eq!(expected, path.to_mermaid());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn staged_path_bytes() {
+        let staged: SeStagedPath = "EP -> TZ x2 + GO  =>  EO -> GL // GP -> OX |  LO -> TX // GX -> LZ |  ELPX -> GOTZ"
+            .parse()
+            .unwrap();
+
+        let bytes = staged.to_bytes();
+
+        //  3 sets of 8 arcospheres, the count, 1 + 5 recipes, and 1 + 2 stages.
+        assert_eq!(34, bytes.len());
+
+        let decoded = SeStagedPath::from_bytes(&bytes).expect("valid");
+
+        assert_eq!(staged, decoded);
+        assert_eq!(staged.stages, decoded.stages);
+
+        assert_eq!(
+            Err(StagedPathDecodeError::UnexpectedEnd),
+            SeStagedPath::from_bytes(&bytes[..bytes.len() - 1])
+        );
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+
+        assert_eq!(
+            Err(StagedPathDecodeError::TrailingBytes),
+            SeStagedPath::from_bytes(&trailing)
+        );
+
+        let mut invalid = bytes;
+        invalid[26] = 0x7F;
+
+        assert_eq!(
+            Err(StagedPathDecodeError::InvalidRecipe { index: 0 }),
+            SeStagedPath::from_bytes(&invalid)
+        );
+
+        //  A single recipe, hence a single stage, yet a boundary past the recipes.
+        let single: SeStagedPath = "LO -> TX  =>  LO -> TX".parse().unwrap();
+
+        let mut out_of_bounds = single.to_bytes();
+
+        assert_eq!(Some(0), out_of_bounds.pop());
+
+        out_of_bounds.extend_from_slice(&[1, 9]);
+
+        assert_eq!(
+            Err(StagedPathDecodeError::InvalidStages),
+            SeStagedPath::from_bytes(&out_of_bounds)
+        );
+    }
+
+    #[test]
+    fn stage_into() {
+        let recipes = [SeArcosphereRecipe::EO, SeArcosphereRecipe::PG];
+        let stage = Stage(&recipes[..]);
+
+        let mut input: SeArcosphereSet = "L".parse().unwrap();
+        let mut output = input;
+
+        stage.input_into(&mut input);
+        stage.output_into(&mut output);
+
+        assert_eq!("EGLOP", input.to_string());
+        assert_eq!("GLLOX", output.to_string());
+
+        assert_eq!("EGOP", stage.input().to_string());
+        assert_eq!("GLOX", stage.output().to_string());
+    }
+
+    #[test]
+    fn staged_path_iter_recipes_with_stage() {
+        use SeArcosphereRecipe::*;
+
+        let path: SeStagedPath = "EGOP -> GLOX  =>  EO -> GL // GP -> OX |  GP -> OX".parse().unwrap();
+
+        let recipes: Vec<_> = path
+            .iter_recipes_with_stage()
+            .map(|(stage, recipe)| (stage, *recipe))
+            .collect();
+
+        assert_eq!(vec![(0, EO), (0, PG), (1, PG)], recipes);
+    }
+
+    #[test]
+    fn staged_path_is_normalized() {
+        let path: SeStagedPath = "EGOP -> GLOX  =>  EO -> GL // GP -> OX |  GP -> OX".parse().unwrap();
+
+        assert!(path.is_normalized());
+
+        let parsed: SeStagedPath = "EGOP -> GLOX  =>  GP -> OX |  EO -> GL".parse().unwrap();
+
+        assert!(SeStagedPath::parallelize(parsed.path).is_normalized());
+
+        let unsorted: SeStagedPath = "EGOP -> GLOX  =>  GP -> OX // EO -> GL |  GP -> OX".parse().unwrap();
+
+        assert!(!unsorted.is_normalized());
+
+        for stages in [vec![0], vec![1, 1], vec![2, 1], vec![3], vec![4]] {
+            let path = SeStagedPath {
+                stages: stages.clone(),
+                ..path.clone()
+            };
+
+            assert!(!path.is_normalized(), "{stages:?}");
+        }
+
+        let empty: SeStagedPath = "EO -> EO  =>".parse().unwrap();
+
+        assert!(empty.is_normalized());
+    }
+
+    #[test]
+    fn staged_path_duplicate_boundary() {
+        assert_eq!(
+            Err(StagedPathParseError::UnexpectedSeparator { index: 1 }),
+            "EGOP -> GLOX  =>  EO -> GL |  |  GP -> OX".parse::<SeStagedPath>()
+        );
+
+        //  A trailing boundary, past the last recipe, is rejected as well.
+        assert_eq!(
+            Err(StagedPathParseError::InvalidRecipe {
+                index: 2,
+                error: RecipeParseError::MissingInput
+            }),
+            "EGOP -> GLOX  =>  EO -> GL |  GP -> OX |".parse::<SeStagedPath>()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn staged_path_duplicate_boundary_decode() {
+        use super::serialization::UncheckedStagedPath;
+
+        let staged: SeStagedPath = "EGOP -> GLOX  =>  EO -> GL |  GP -> OX".parse().unwrap();
+
+        let mut bytes = staged.to_bytes();
+
+        assert_eq!(Some(1), bytes.pop());
+
+        assert_eq!(Some(1), bytes.pop());
+
+        bytes.extend_from_slice(&[2, 1, 1]);
+
+        assert_eq!(
+            Err(StagedPathDecodeError::InvalidStages),
+            SeStagedPath::from_bytes(&bytes)
+        );
+
+        let unchecked = UncheckedStagedPath {
+            path: staged.path.clone(),
+            stages: vec![1, 1],
+        };
+
+        assert_eq!(
+            Err(StagedPathDecodeError::InvalidStages),
+            SeStagedPath::try_from(unchecked)
+        );
+    }
+
+    #[test]
+    fn staged_path_from_str_with() {
+        let grammar = PathGrammar {
+            arrow: ">",
+            recipes: ":",
+            parallel: ",",
+            stage: ";",
+        };
+
+        let expected: SeStagedPath = "EGOP -> GLOX  =>  EO -> GL // GP -> OX |  GP -> OX".parse().unwrap();
+
+        let path = SeStagedPath::from_str_with("EGOP > GLOX : EO > GL , GP > OX ; GP > OX", &grammar).expect("success");
+
+        assert_eq!(expected, path);
+
+        assert_eq!(
+            Err(StagedPathParseError::InvalidSeparator { index: 0 }),
+            SeStagedPath::from_str_with("EGOP > GLOX : EO > GL | GP > OX", &grammar)
+        );
+    }
+
+    #[test]
+    fn staged_path_round_trip() {
+        //  Xorshift, for a reproducible sequence of arbitrary paths.
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+
+        let mut next = |bound: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+
+            (state % bound as u64) as usize
+        };
+
+        let set = |minimum: usize, next: &mut dyn FnMut(usize) -> usize| {
+            let mut set = SeArcosphereSet::new();
+
+            for _ in 0..(minimum + next(6)) {
+                set.insert(SeArcosphere::from_index(next(SeArcosphere::DIMENSION)));
+            }
+
+            set
+        };
+
+        for _ in 0..1_000 {
+            let source = set(1, &mut next);
+            let target = set(1, &mut next);
+            let catalysts = set(0, &mut next);
+            let count = NonZeroU8::new(1 + next(255) as u8).unwrap();
+
+            let recipes: Vec<_> = (0..next(12))
+                .map(|_| SeArcosphereRecipe::from_index(next(SeArcosphereRecipe::DIMENSION)))
+                .collect();
+
+            //  Each stage is non-empty, hence starts strictly after the previous one, and before the end.
+            let stages = (1..recipes.len()).filter(|_| next(3) == 0).map(|i| i as u8).collect();
+
+            let staged = SeStagedPath {
+                path: Path {
+                    source,
+                    target,
+                    count,
+                    catalysts,
+                    recipes,
+                },
+                stages,
+            };
+
+            let display = staged.to_string();
+
+            assert_eq!(Ok(&staged), display.parse::<SeStagedPath>().as_ref(), "{display}");
+        }
+    }
+
+    #[test]
+    fn staged_path_compact() {
+        let verifier = Verifier::new(SeArcosphereFamily);
+
+        //  Both recipes are independent, yet split in two stages.
+        let split: SeStagedPath = "EGOP -> GLOX  =>  EO -> GL |  GP -> OX".parse().unwrap();
+
+        let compacted = split.compact();
+
+        assert_eq!(1, split.stages.len());
+        assert_eq!(0, compacted.stages.len());
+        assert_eq!("EGOP -> GLOX  =>  EO -> GL // GP -> OX", compacted.to_string());
+
+        verifier.verify(&compacted).expect("valid");
+
+        //  The second stage depends on the output of the first, hence nothing to merge.
+        let dependent: SeStagedPath = "EP -> LX + G  =>  GP -> OX |  EO -> GL".parse().unwrap();
+
+        assert_eq!(dependent, dependent.compact());
+    }
+
+    #[test]
+    fn staged_path_counts() {
+        let set = "LO".parse().unwrap();
+
+        let trivial = SeStagedPath {
+            path: Path {
+                source: set,
+                target: set,
+                count: ONE,
+                catalysts: SeArcosphereSet::new(),
+                recipes: Vec::new(),
+            },
+            stages: Vec::new(),
+        };
+
+        assert_eq!(0, trivial.recipe_count());
+        assert_eq!(0, trivial.stage_count());
+
+        let cases = [
+            ("EO -> LG  =>  EO -> LG", 1, 1),
+            (
+                "LO -> ET x2 + GX  =>  LO -> TX // LO -> TX // GX -> LZ |  LT -> EZ // XZ -> PT |  PZ -> EG",
+                6,
+                3,
+            ),
+        ];
+
+        for (path, recipes, stages) in cases {
+            let path: SeStagedPath = path.parse().unwrap();
+
+            assert_eq!(recipes, path.recipe_count(), "{path}");
+            assert_eq!(stages, path.stage_count(), "{path}");
+        }
+    }
+
+    #[test]
+    fn path_inverse() {
+        let source: SeArcosphereSet = "ELPX".parse().unwrap();
+        let target: SeArcosphereSet = "GOTZ".parse().unwrap();
+
+        let verifier = Verifier::new(SeArcosphereFamily);
+
+        let staged: SeStagedPath = "ELPX -> GOTZ  =>  ELPX -> GOTZ".parse().unwrap();
+
+        let inverse = staged.inverse().expect("inversions only");
+
+        assert_eq!(target, inverse.path.source);
+        assert_eq!(source, inverse.path.target);
+
+        verifier.verify(&inverse).expect("valid");
+
+        let inverse = staged.path.inverse().expect("inversions only");
+
+        verifier.verify_path(&inverse).expect("valid");
+
+        let staged: SeStagedPath = "EP -> LX + GO  =>  EO -> GL // GP -> OX".parse().unwrap();
+
+        assert_eq!(Err(RecipeIdentifyError::UnknownRecipe), staged.inverse().map(|_| ()));
+    }
+
+    #[test]
+    fn path_then() {
+        let source: SeArcosphereSet = "EP".parse().unwrap();
+        let target: SeArcosphereSet = "GT".parse().unwrap();
+
+        let first: SeStagedPath = "EP -> LX + GO  =>  EO -> GL // GP -> OX".parse().unwrap();
+        let second: SeStagedPath = "LX -> GT x4 + ELP  =>  ELPX -> GOTZ |  LO -> TX // LT -> EZ // XZ -> PT |  \
+            ELPX -> GOTZ // LT -> EZ // XZ -> PT |  EO -> GL // PZ -> EG // XZ -> PT"
+            .parse()
+            .unwrap();
+
+        let (first, second) = (first.path, second.path);
+
+        let composed = first.then(&second).expect("composable");
+
+        assert_eq!(source, composed.source);
+        assert_eq!(target, composed.target);
+
+        let verifier = Verifier::new(SeArcosphereFamily);
+
+        verifier.verify_path(&composed).expect("valid");
+        verifier.verify(&SeStagedPath::parallelize(composed)).expect("valid");
+
+        let result = second.then(&first).map(|_| ());
+
+        assert_eq!(Err(CompositionError::MismatchedEndpoints), result);
+    }
+} // mod tests
@@ -6,7 +6,9 @@
 //!
 //! This decomposition helps planning how to arrange the various Gravimetrics facilities to actually execute the path.
 
-use core::{error, fmt};
+use core::{error, fmt, fmt::Write};
+
+use alloc::{collections::BTreeMap, format, string::String, vec, vec::Vec};
 
 use crate::model::{ArcosphereFamily, ArcosphereSet, StagedPath};
 
@@ -24,6 +26,115 @@ where
     pub stages: Vec<StageDescription<F>>,
 }
 
+impl<F> Plan<F>
+where
+    F: ArcosphereFamily,
+{
+    /// Renders the plan as a Graphviz DOT graph.
+    ///
+    /// Each stage is a node, labelled with its recipes, and the arcospheres flowing between stages are labelled edges:
+    ///
+    /// -   Solid edges denote the arcospheres consumed by the next stage.
+    /// -   Dashed edges denote the remainder, carried forward past the next stage.
+    /// -   Bold edges denote the arcospheres extracted as targets or catalysts.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+
+        let path = &self.path.path;
+
+        let source = path.source * path.count + path.catalysts;
+        let output = path.target * path.count + path.catalysts;
+
+        let _ = writeln!(dot, "digraph plan {{");
+        let _ = writeln!(dot, "    rankdir=LR;");
+        let _ = writeln!(dot, "    node [shape=box];");
+        let _ = writeln!(dot);
+        let _ = writeln!(dot, "    source [label=\"{source}\", shape=ellipse];");
+
+        for (i, stage) in self.path.stages().enumerate() {
+            let i = i + 1;
+
+            let _ = write!(dot, "    stage{i} [label=\"{i}.");
+
+            for recipe in stage.0 {
+                let _ = write!(dot, "\\n{recipe}");
+            }
+
+            let _ = writeln!(dot, "\"];");
+        }
+
+        let _ = writeln!(dot, "    target [label=\"{output}\", shape=ellipse];");
+        let _ = writeln!(dot);
+
+        let mut extracted = F::Set::default();
+        let mut previous = String::from("source");
+
+        for (i, (desc, stage)) in self.stages.iter().zip(self.path.stages()).enumerate() {
+            let current = format!("stage{}", i + 1);
+
+            let _ = writeln!(dot, "    {previous} -> {current} [label=\"{}\"];", stage.input());
+
+            let remainder = desc.remainder;
+
+            if !remainder.is_empty() {
+                let _ = writeln!(
+                    dot,
+                    "    {previous} -> {current} [label=\"{remainder}\", style=dashed];"
+                );
+            }
+
+            let fresh = desc.extracted - extracted;
+
+            if !fresh.is_empty() {
+                let _ = writeln!(dot, "    {previous} -> target [label=\"{fresh}\", style=bold];");
+            }
+
+            extracted = desc.extracted;
+            previous = current;
+        }
+
+        let last = output - extracted;
+
+        if !last.is_empty() {
+            let _ = writeln!(dot, "    {previous} -> target [label=\"{last}\", style=bold];");
+        }
+
+        let _ = writeln!(dot, "}}");
+
+        dot
+    }
+
+    /// Returns the number of facilities required by each recipe.
+    ///
+    /// Since the recipes of a stage are executed in parallel, a recipe appearing 3 times in a single stage requires 3
+    /// facilities, hence the number of facilities of a recipe is its maximum number of occurrences in any stage.
+    pub fn facility_count(&self) -> BTreeMap<F::Recipe, usize> {
+        let mut facilities = BTreeMap::new();
+
+        for stage in self.path.stages() {
+            let mut occurrences = BTreeMap::new();
+
+            for recipe in stage.0 {
+                *occurrences.entry(*recipe).or_insert(0) += 1;
+            }
+
+            for (recipe, count) in occurrences {
+                let maximum = facilities.entry(recipe).or_insert(0);
+
+                *maximum = count.max(*maximum);
+            }
+        }
+
+        facilities
+    }
+
+    /// Returns the maximum number of arcospheres buffered between stages, that is the largest remainder & extracted
+    /// arcospheres of any stage, or 0 if there is no stage.
+    pub fn max_buffer(&self) -> usize {
+        self.stages.iter().map(StageDescription::len).max().unwrap_or(0)
+    }
+}
+
 impl<F> fmt::Display for Plan<F>
 where
     F: ArcosphereFamily,
@@ -32,13 +143,15 @@ where
         for (i, (desc, stage)) in self.stages.iter().zip(self.path.stages()).enumerate() {
             let i = i + 1;
 
-            writeln!(
-                f,
-                "{i:2}.  [{}] + [{}] + [{}] | {stage}",
-                desc.remainder,
-                stage.input(),
-                desc.extracted
-            )?;
+            write!(f, "{i:2}.  [")?;
+            fmt::Display::fmt(&desc.remainder, f)?;
+            f.write_str("] + [")?;
+            fmt::Display::fmt(&stage.input(), f)?;
+            f.write_str("] + [")?;
+            fmt::Display::fmt(&desc.extracted, f)?;
+            f.write_str("] | ")?;
+            fmt::Display::fmt(&stage, f)?;
+            writeln!(f)?;
         }
 
         Ok(())
@@ -51,20 +164,26 @@ pub struct StageDescription<F>
 where
     F: ArcosphereFamily,
 {
-    //
-    //  Should this be split up further?
-    //
-    //  -   input: arcospheres still remaining from the source & catalysts.
-    //  -   passed: arcospheres produced by the before-previous stages, not used in this stage.
-    //  -   sidelined: arcospheres produced by the previous stage, not used in this stage.
-    //  -   recovered: arcospheres produced by the before-previous stages, to be immediately used in this stage.
-    //  -   fresh: arcospheres produced by the previous stage, to be immediately used in this stage.
-    //  -   extracted: arcospheres produced by the previous stage, and to be extracted as targets or catalysts.
-    //  -   output: arcospheres produced by the before-previous stages, and already extracted as targets or catalysts.
+    //  The remainder is further split up into input + passed + sidelined, while the input of the stage is split up
+    //  into the arcospheres taken from the source & catalysts + recovered + fresh.
     /// Arcospheres available at the start of the stage, minus those used by the stage.
     pub remainder: F::Set,
     /// Arcospheres extracted by previous stages, as targets or catalysts.
     pub extracted: F::Set,
+    /// Arcospheres still remaining from the source & catalysts, not used in this stage.
+    pub input: F::Set,
+    /// Arcospheres produced by the before-previous stages, not used in this stage.
+    pub passed: F::Set,
+    /// Arcospheres produced by the previous stage, not used in this stage.
+    pub sidelined: F::Set,
+    /// Arcospheres produced by the before-previous stages, to be immediately used in this stage.
+    pub recovered: F::Set,
+    /// Arcospheres produced by the previous stage, to be immediately used in this stage.
+    pub fresh: F::Set,
+    /// Arcospheres already extracted, as targets or catalysts, prior to the previous stage.
+    ///
+    /// This is a subset of `extracted`, the difference being extracted from the output of the previous stage.
+    pub output: F::Set,
 }
 
 impl<F> StageDescription<F>
@@ -92,10 +211,31 @@ where
         Self {
             remainder: set,
             extracted: set,
+            input: set,
+            passed: set,
+            sidelined: set,
+            recovered: set,
+            fresh: set,
+            output: set,
         }
     }
 }
 
+/// Timing estimate of the execution of a plan.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct PlanTiming {
+    /// The duration of each stage, that is the maximum duration of its recipes.
+    pub stages: Vec<u32>,
+    /// The length of the critical path, that is the sum of the durations of all stages.
+    pub latency: u64,
+    /// The index of the bottleneck stage, that is the busiest stage.
+    pub bottleneck: usize,
+    /// The duration of the bottleneck stage.
+    ///
+    /// In steady state, one execution of the path completes every `period`.
+    pub period: u32,
+}
+
 /// Error which may occur during the planning.
 #[derive(Clone, Copy, Debug)]
 pub enum PlanningError<F>
@@ -166,14 +306,57 @@ where
 
         let extracteds = Self::compute_extracteds(&mut remainders, &staged);
 
-        let stages = remainders
-            .into_iter()
-            .zip(extracteds)
-            .map(|(remainder, extracted)| StageDescription { remainder, extracted })
-            .collect();
+        let stages = Self::compute_flows(&remainders, &extracteds, &staged);
 
         Ok(Plan { path: staged, stages })
     }
+
+    /// Computes, for each arcosphere of the output -- targets & catalysts -- the earliest stage at which it can be
+    /// extracted, if the path is correct.
+    ///
+    /// A sphere available at stage `i` is extracted prior to executing stage `i`, hence 0 denotes a sphere extracted
+    /// straight from the source & catalysts, and the number of stages a sphere only available at the very end.
+    pub fn extraction_schedule(&self, staged: &StagedPath<F>) -> Result<Vec<(F::Arcosphere, usize)>, PlanningError<F>> {
+        let mut remainders = Self::compute_remainders(staged)?;
+
+        Ok(Self::compute_schedule(&mut remainders, staged))
+    }
+
+    /// Creates an execution plan for the path, if correct, and estimates its timing.
+    ///
+    /// The recipes of a stage are executed in parallel, hence the duration of a stage is the maximum duration of its
+    /// recipes.
+    pub fn plan_timed<D>(
+        &self,
+        staged: StagedPath<F>,
+        recipe_duration: D,
+    ) -> Result<(Plan<F>, PlanTiming), PlanningError<F>>
+    where
+        D: Fn(&F::Recipe) -> u32,
+    {
+        let stages: Vec<_> = staged
+            .stages()
+            .map(|stage| stage.0.iter().map(&recipe_duration).max().unwrap_or_default())
+            .collect();
+
+        let latency = stages.iter().map(|d| *d as u64).sum();
+
+        let period = stages.iter().copied().max().unwrap_or_default();
+
+        //  In case of ties, the earliest stage is the bottleneck.
+        let bottleneck = stages.iter().position(|d| *d == period).unwrap_or_default();
+
+        let plan = self.plan(staged)?;
+
+        let timing = PlanTiming {
+            stages,
+            latency,
+            bottleneck,
+            period,
+        };
+
+        Ok((plan, timing))
+    }
 }
 
 //
@@ -195,26 +378,25 @@ where
         for (index, stage) in staged.stages().enumerate() {
             let input = stage.input();
 
-            if !input.is_subset_of(&step) {
+            let Some(remainder) = step.checked_sub(input) else {
                 return Err(PlanningError::FailedApplication {
                     index,
                     current: step,
                     input,
                 });
-            }
+            };
 
-            remainders.push(step - input);
+            remainders.push(remainder);
 
-            step = step - input + stage.output();
+            step = remainder;
+            stage.output_into(&mut step);
         }
 
         let target = staged.path.target * staged.path.count;
 
-        if !target.is_subset_of(&step) {
+        let Some(remainder) = step.checked_sub(target) else {
             return Err(PlanningError::FailedTarget { result: step });
-        }
-
-        let remainder = step - target;
+        };
 
         if remainder != staged.path.catalysts {
             return Err(PlanningError::FailedCatalysts { remainder });
@@ -223,7 +405,9 @@ where
         Ok(remainders)
     }
 
-    fn compute_extracteds(remainders: &mut [F::Set], staged: &StagedPath<F>) -> Vec<F::Set> {
+    //  Computes the earliest stage at which each arcosphere of the output can be extracted, removing it from the
+    //  remainders of this stage onwards.
+    fn compute_schedule(remainders: &mut [F::Set], staged: &StagedPath<F>) -> Vec<(F::Arcosphere, usize)> {
         fn find_earliest_extraction_stage<S>(remainders: &[S], element: S::Arcosphere) -> usize
         where
             S: ArcosphereSet,
@@ -237,15 +421,315 @@ where
 
         let output = staged.path.target * staged.path.count + staged.path.catalysts;
 
-        let mut extracteds = vec![F::Set::default(); remainders.len()];
+        let mut schedule = Vec::with_capacity(output.len());
 
         for sphere in output {
             let earliest = find_earliest_extraction_stage(remainders, sphere);
 
-            extracteds.iter_mut().skip(earliest).for_each(|e| e.insert(sphere));
             remainders.iter_mut().skip(earliest).for_each(|r| r.remove(sphere));
+
+            schedule.push((sphere, earliest));
+        }
+
+        schedule
+    }
+
+    fn compute_extracteds(remainders: &mut [F::Set], staged: &StagedPath<F>) -> Vec<F::Set> {
+        let mut extracteds = vec![F::Set::default(); remainders.len()];
+
+        for (sphere, earliest) in Self::compute_schedule(remainders, staged) {
+            extracteds.iter_mut().skip(earliest).for_each(|e| e.insert(sphere));
         }
 
         extracteds
     }
+
+    //  Computes the detailed flow of arcospheres, for each stage.
+    //
+    //  The arcospheres used by a stage are preferably taken from the previous stage, then the before-previous stages,
+    //  and finally the source & catalysts, so as to minimize buffering. The same preference applies to extraction.
+    fn compute_flows(remainders: &[F::Set], extracteds: &[F::Set], staged: &StagedPath<F>) -> Vec<StageDescription<F>> {
+        //  Removes as many arcospheres of `wanted` as possible from `pool`, returning them.
+        fn take<S>(pool: &mut S, wanted: &mut S) -> S
+        where
+            S: ArcosphereSet,
+        {
+            let mut taken = S::default();
+
+            for sphere in *wanted {
+                if pool.try_remove(sphere).is_ok() {
+                    wanted.remove(sphere);
+                    taken.insert(sphere);
+                }
+            }
+
+            taken
+        }
+
+        let mut source = staged.path.source * staged.path.count + staged.path.catalysts;
+        let mut earlier = F::Set::default();
+        let mut previous = F::Set::default();
+
+        let mut output = F::Set::default();
+
+        let mut descriptions = Vec::with_capacity(remainders.len());
+
+        for ((remainder, extracted), stage) in remainders.iter().zip(extracteds).zip(staged.stages()) {
+            let mut wanted = stage.input();
+
+            let fresh = take(&mut previous, &mut wanted);
+            let recovered = take(&mut earlier, &mut wanted);
+            take(&mut source, &mut wanted);
+
+            debug_assert!(wanted.is_empty(), "{wanted}");
+
+            let mut extracting = *extracted - output;
+
+            take(&mut previous, &mut extracting);
+            take(&mut earlier, &mut extracting);
+            take(&mut source, &mut extracting);
+
+            debug_assert!(extracting.is_empty(), "{extracting}");
+            debug_assert!(*remainder == source + earlier + previous);
+
+            descriptions.push(StageDescription {
+                remainder: *remainder,
+                extracted: *extracted,
+                input: source,
+                passed: earlier,
+                sidelined: previous,
+                recovered,
+                fresh,
+                output,
+            });
+
+            earlier += previous;
+            previous = stage.output();
+            output = *extracted;
+        }
+
+        descriptions
+    }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec};
+
+    use crate::{
+        model::ArcosphereRecipe,
+        space_exploration::{SeArcosphere, SeArcosphereFamily, SeArcosphereRecipe, SeStagedPath},
+    };
+
+    use super::*;
+
+    #[test]
+    fn plan_extraction_schedule() {
+        use SeArcosphere::*;
+
+        let planner = Planner::new(SeArcosphereFamily);
+
+        //  A spare Gamma is always available, whereas the other spheres are only produced along the way.
+        let path: SeStagedPath = "EGOP -> GLOX  =>  EO -> GL |  GP -> OX".parse().unwrap();
+
+        let schedule = planner.extraction_schedule(&path).expect("valid");
+
+        assert_eq!(vec![(Gamma, 0), (Lambda, 1), (Omega, 2), (Xi, 2)], schedule);
+
+        //  The spheres extracted are those of the plan.
+        let plan = planner.plan(path).expect("valid");
+
+        for (sphere, earliest) in schedule {
+            let stages = &plan.stages[earliest..];
+
+            assert!(stages.iter().all(|stage| stage.extracted.contains(sphere)));
+        }
+    }
+
+    #[test]
+    fn plan_facility_count() {
+        let path = "LO -> ET x2 + GX  =>  LO -> TX // LO -> TX // GX -> LZ |  LT -> EZ // XZ -> PT |  PZ -> EG";
+        let path: SeStagedPath = path.parse().unwrap();
+
+        let plan = Planner::new(SeArcosphereFamily).plan(path).expect("valid");
+
+        let expected: Vec<_> = [
+            (SeArcosphereRecipe::LO, 2),
+            (SeArcosphereRecipe::LT, 1),
+            (SeArcosphereRecipe::PZ, 1),
+            (SeArcosphereRecipe::XG, 1),
+            (SeArcosphereRecipe::XZ, 1),
+        ]
+        .into_iter()
+        .collect();
+
+        let facilities: Vec<_> = plan.facility_count().into_iter().collect();
+
+        assert_eq!(expected, facilities);
+    }
+
+    #[test]
+    fn plan_display_fancy() {
+        let path: SeStagedPath = "GLZ -> LOT + P  =>  GP -> OX |  XZ -> PT".parse().unwrap();
+
+        let plan = Planner::new(SeArcosphereFamily).plan(path).expect("valid");
+
+        let plain = " 1.  [Z] + [GP] + [L] | GP -> OX\n 2.  [] + [XZ] + [LO] | XZ -> PT\n";
+        let fancy = " 1.  [ζ] + [γφ] + [λ] | γφ -> ωξ\n 2.  [] + [ξζ] + [λω] | ξζ -> φθ\n";
+
+        assert_eq!(plain, plan.to_string());
+        assert_eq!(fancy, format!("{plan:#}"));
+    }
+
+    #[test]
+    fn plan_max_buffer() {
+        let path = "LO -> ET x2 + GX  =>  LO -> TX // LO -> TX // GX -> LZ |  LT -> EZ // XZ -> PT |  PZ -> EG";
+        let path: SeStagedPath = path.parse().unwrap();
+
+        let plan = Planner::new(SeArcosphereFamily).plan(path).expect("valid");
+
+        //  The third stage buffers the ETTX extracted by the previous stages.
+        assert_eq!(4, plan.max_buffer());
+        assert_eq!(plan.stages[2].len(), plan.max_buffer());
+    }
+
+    #[test]
+    fn plan_error() {
+        let planner = Planner::new(SeArcosphereFamily);
+
+        //  Without the catalyst, the first stage cannot be applied.
+        let path: SeStagedPath = "EP -> LX  =>  PG -> XO |  EO -> LG".parse().unwrap();
+
+        let failed = matches!(
+            planner.plan(path),
+            Err(PlanningError::FailedApplication { index: 0, .. })
+        );
+
+        assert!(failed);
+
+        //  Without the second stage, the target is not reached.
+        let path: SeStagedPath = "EP -> LX + G  =>  PG -> XO".parse().unwrap();
+
+        let Err(PlanningError::FailedTarget { result }) = planner.plan(path) else {
+            panic!("Expected FailedTarget");
+        };
+
+        assert_eq!("EOX", result.to_string());
+
+        //  The target is reached, at the cost of the catalyst.
+        let path: SeStagedPath = "EG -> GL + O  =>  EO -> GL".parse().unwrap();
+
+        let Err(PlanningError::FailedCatalysts { remainder }) = planner.plan(path) else {
+            panic!("Expected FailedCatalysts");
+        };
+
+        assert_eq!("G", remainder.to_string());
+    }
+
+    #[test]
+    fn plan_flows() {
+        let set = |s: &str| s.parse().unwrap();
+
+        let path: SeStagedPath = "EP -> LX + G  =>  PG -> XO |  EO -> LG".parse().unwrap();
+
+        let plan = Planner::new(SeArcosphereFamily).plan(path).expect("valid");
+
+        //  The E is carried past the first stage, whose O is immediately used by the second, while its X is extracted.
+        let first = StageDescription {
+            remainder: set("E"),
+            input: set("E"),
+            ..StageDescription::default()
+        };
+
+        let second = StageDescription {
+            extracted: set("X"),
+            fresh: set("O"),
+            ..StageDescription::default()
+        };
+
+        assert_eq!(vec![first, second], plan.stages);
+
+        let path = "LO -> ET x2 + GX  =>  LO -> TX // LO -> TX // GX -> LZ |  LT -> EZ // XZ -> PT |  PZ -> EG";
+        let path: SeStagedPath = path.parse().unwrap();
+
+        let plan = Planner::new(SeArcosphereFamily).plan(path).expect("valid");
+
+        for (desc, stage) in plan.stages.iter().zip(plan.path.stages()) {
+            assert_eq!(desc.remainder, desc.input + desc.passed + desc.sidelined);
+            assert!(desc.output.is_subset_of(&desc.extracted));
+            assert!((desc.recovered + desc.fresh).is_subset_of(&stage.input()));
+        }
+
+        assert_eq!(set("LTXZ"), plan.stages[1].fresh);
+        assert_eq!(set("PZ"), plan.stages[2].fresh);
+        assert_eq!(set("TX"), plan.stages[2].output);
+    }
+
+    #[test]
+    fn plan_timed() {
+        let planner = Planner::new(SeArcosphereFamily);
+
+        let path = "LO -> ET x2 + GX  =>  LO -> TX // LO -> TX // GX -> LZ |  LT -> EZ // XZ -> PT |  PZ -> EG";
+        let path: SeStagedPath = path.parse().unwrap();
+
+        //  The inversions take longer, the foldings using a Zeta longer still.
+        let duration = |recipe: &SeArcosphereRecipe| {
+            let zetas = recipe.input().into_iter().filter(|s| *s == SeArcosphere::Zeta).count() as u32;
+
+            if recipe.is_inversion() {
+                10
+            } else {
+                2 + 3 * zetas
+            }
+        };
+
+        let (plan, timing) = planner.plan_timed(path.clone(), duration).expect("valid");
+
+        assert_eq!(planner.plan(path).expect("valid"), plan);
+
+        let expected = PlanTiming {
+            stages: vec![2, 5, 5],
+            latency: 12,
+            bottleneck: 1,
+            period: 5,
+        };
+
+        assert_eq!(expected, timing);
+
+        //  The timing is only estimated for valid paths.
+        let path: SeStagedPath = "EP -> LX  =>  PG -> XO |  EO -> LG".parse().unwrap();
+
+        let failed = matches!(
+            planner.plan_timed(path, duration),
+            Err(PlanningError::FailedApplication { index: 0, .. })
+        );
+
+        assert!(failed);
+    }
+
+    #[test]
+    fn plan_to_dot() {
+        let path: SeStagedPath = "EP -> LX + G  =>  PG -> XO |  EO -> LG".parse().unwrap();
+
+        let plan = Planner::new(SeArcosphereFamily).plan(path).expect("valid");
+
+        let expected = r#"digraph plan {
+    rankdir=LR;
+    node [shape=box];
+
+    source [label="EGP", shape=ellipse];
+    stage1 [label="1.\nGP -> OX"];
+    stage2 [label="2.\nEO -> GL"];
+    target [label="GLX", shape=ellipse];
+
+    source -> stage1 [label="GP"];
+    source -> stage1 [label="E", style=dashed];
+    stage1 -> stage2 [label="EO"];
+    stage1 -> target [label="X", style=bold];
+    stage2 -> target [label="GL", style=bold];
+}
+"#;
+
+        assert_eq!(expected, plan.to_dot());
+    }
+} // mod tests
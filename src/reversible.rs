@@ -0,0 +1,202 @@
+//! Reversible family, wrapping another family.
+//!
+//! A reversible family extends the recipes of the wrapped family with the reverse of each of its folding recipes, for
+//! setups in which folding can also be performed backward.
+//!
+//! The inversion recipes are left as is, and so are the folding recipes whose reverse already is a recipe of the wrapped
+//! family: their reverse does not exist.
+
+use core::{fmt, str};
+
+use crate::model::{ArcosphereFamily, ArcosphereRecipe, RecipeParseError, SetParseError};
+
+/// Reversible family, wrapping `F`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Reversible<F>(pub F);
+
+impl<F> ArcosphereFamily for Reversible<F>
+where
+    F: ArcosphereFamily<Set: str::FromStr<Err = SetParseError>>,
+{
+    type Arcosphere = F::Arcosphere;
+    type Set = F::Set;
+    type Recipe = ReversibleRecipe<F::Recipe>;
+}
+
+/// Recipe of a reversible family.
+///
+/// The first `R::DIMENSION` indexes are the recipes of the wrapped family, as is, and the next `R::DIMENSION` indexes
+/// their reverse, in the same order. The indexes of the reverse of an inversion, or of a folding which already has a
+/// reverse, map to no recipe.
+///
+/// As for the recipes of a dynamic family, a reversible recipe carries its input & output, so as not to search for the
+/// reverse of the recipe in the midst of a search.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ReversibleRecipe<R>
+where
+    R: ArcosphereRecipe,
+{
+    recipe: R,
+    reversed: bool,
+    input: R::Set,
+    output: R::Set,
+}
+
+impl<R> ReversibleRecipe<R>
+where
+    R: ArcosphereRecipe,
+{
+    /// Returns the recipe of the wrapped family, as is, or whose reverse this recipe is.
+    pub fn recipe(&self) -> R {
+        self.recipe
+    }
+
+    /// Returns whether this recipe is the reverse of the recipe of the wrapped family.
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+}
+
+impl<R> ArcosphereRecipe for ReversibleRecipe<R>
+where
+    R: ArcosphereRecipe<Set: str::FromStr<Err = SetParseError>>,
+{
+    const DIMENSION: usize = 2 * R::DIMENSION;
+
+    type Arcosphere = R::Arcosphere;
+    type Set = R::Set;
+
+    fn from_index(index: usize) -> Self {
+        assert!(index < Self::DIMENSION, "expect index to be in 0..{}", Self::DIMENSION);
+
+        Self::try_from_index(index).expect("index to map to a recipe")
+    }
+
+    fn try_from_index(index: usize) -> Option<Self> {
+        if index < R::DIMENSION {
+            let recipe = R::try_from_index(index)?;
+
+            return Some(Self {
+                recipe,
+                reversed: false,
+                input: recipe.input(),
+                output: recipe.output(),
+            });
+        }
+
+        let recipe = R::try_from_index(index.checked_sub(R::DIMENSION)?)?;
+
+        //  Reversing an inversion, or a folding which already has a reverse, would only duplicate an existing recipe.
+        if recipe.is_inversion() || recipe.reverse().is_ok() {
+            return None;
+        }
+
+        let (input, output) = recipe.reversed();
+
+        Some(Self {
+            recipe,
+            reversed: true,
+            input,
+            output,
+        })
+    }
+
+    fn into_index(self) -> usize {
+        let index = self.recipe.into_index();
+
+        if self.reversed {
+            index + R::DIMENSION
+        } else {
+            index
+        }
+    }
+
+    fn input(&self) -> Self::Set {
+        self.input
+    }
+
+    fn output(&self) -> Self::Set {
+        self.output
+    }
+}
+
+impl<R> fmt::Display for ReversibleRecipe<R>
+where
+    R: ArcosphereRecipe<Set: str::FromStr<Err = SetParseError>>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        self.display(f)
+    }
+}
+
+impl<R> str::FromStr for ReversibleRecipe<R>
+where
+    R: ArcosphereRecipe<Set: str::FromStr<Err = SetParseError>>,
+{
+    type Err = RecipeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use crate::space_exploration::{SeArcosphereFamily, SeArcosphereRecipe};
+
+    use super::*;
+
+    type Recipe = ReversibleRecipe<SeArcosphereRecipe>;
+
+    #[test]
+    fn family_reversible() {
+        assert_eq!(Ok(()), Reversible::<SeArcosphereFamily>::validate());
+        assert_eq!(2 * SeArcosphereRecipe::DIMENSION, Recipe::DIMENSION);
+
+        for recipe in Recipe::all() {
+            assert_eq!(Some(recipe), Recipe::try_from_index(recipe.into_index()));
+        }
+
+        //  Only the foldings without a reverse of their own gain one.
+        let reversible = SeArcosphereRecipe::all()
+            .filter(|recipe| recipe.is_folding() && recipe.reverse().is_err())
+            .count();
+
+        assert_eq!(SeArcosphereRecipe::DIMENSION + reversible, Recipe::all().count());
+
+        //  The reverse of a folding is a recipe of its own.
+        let recipe: Recipe = "GL -> EO".parse().unwrap();
+
+        assert!(recipe.is_reversed());
+        assert_eq!(SeArcosphereRecipe::EO, recipe.recipe());
+        assert_eq!("GL -> EO", recipe.to_string());
+
+        //  The reverse of an inversion does not exist.
+        let inversion = SeArcosphereRecipe::DIMENSION + SeArcosphereRecipe::GOTZ.into_index();
+
+        assert_eq!(None, Recipe::try_from_index(inversion));
+        assert_eq!(None, Recipe::try_from_index(Recipe::DIMENSION));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn solve_reversible() {
+        use alloc::vec;
+
+        use crate::{executor::DefaultExecutor, solver::Solver};
+
+        let recipe: Recipe = "GL -> EO".parse().unwrap();
+
+        //  The reverse of the folding is directly available.
+        let solver = Solver::<_, DefaultExecutor>::new(Reversible(SeArcosphereFamily));
+
+        let (source, target) = ("GL".parse().unwrap(), "EO".parse().unwrap());
+
+        let paths = solver.solve(source, target).expect("success");
+
+        assert_eq!(1, paths.len());
+        assert_eq!(vec![recipe], paths[0].path.recipes);
+    }
+} // mod tests
@@ -2,12 +2,19 @@
 
 use core::{
     cmp::{self, Reverse},
-    error, fmt,
+    error, fmt, iter, mem,
     num::NonZeroU8,
-    ops::Range,
+    ops::{Range, RangeInclusive},
+    slice,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::Duration,
 };
 
-use fxhash::{FxHashMap, FxHashSet};
+use std::{
+    sync::{Arc, Mutex, MutexGuard},
+    time::Instant,
+    vec,
+};
 
 use crate::{
     executor::Executor,
@@ -15,17 +22,55 @@ use crate::{
     space_exploration::SeArcosphereFamily,
 };
 
+/// The builder of the hashers of the maps & sets of the solver, as selected by the features.
+///
+/// When several hashing features are enabled, "std-hash" takes precedence over "ahash", itself taking precedence over
+/// "fxhash". Without any, the hashing of `std` is used.
+pub type HashBuilder = hashing::HashBuilder;
+
+/// The map used by the solver.
+pub type HashMap<K, V> = std::collections::HashMap<K, V, HashBuilder>;
+
+/// The set used by the solver.
+pub type HashSet<T> = std::collections::HashSet<T, HashBuilder>;
+
 /// Error which may occur during the search for a solution.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum ResolutionError {
     /// There is no solution, as the number of arcospheres is not preserved.
     PreservationError,
+    /// There is no solution, as no combination of the allowed recipes balances the source & target, regardless of the
+    /// catalysts.
+    Infeasible,
     /// There is no solution for the given range of number of catalysts.
-    OutsideCatalysts,
+    OutsideCatalysts {
+        /// The maximum number of catalysts explored.
+        maximum_catalysts: u8,
+    },
+    /// There is no solution for the numbers of catalysts within the maximum number of permutations of catalysts.
+    OutsidePermutations {
+        /// The maximum number of sets of catalysts generated for a given number of catalysts.
+        maximum_catalyst_permutations: usize,
+    },
     /// There is no solution for the given range of number of repetitions.
-    OutsideCount,
-    /// There is no solution for the given range of number of recipes.
-    OutsideRecipes,
+    OutsideCount {
+        /// The maximum number of repetitions explored.
+        maximum_repetitions: u8,
+    },
+    /// There is no solution for the given range of number of recipes, or with the allowed recipes.
+    OutsideRecipes {
+        /// The maximum number of recipes explored.
+        maximum_recipes: u8,
+    },
+    /// There are solutions, but all of them exceed the maximum number of stages.
+    OutsideStages {
+        /// The maximum number of stages allowed.
+        maximum_stages: u8,
+    },
+    /// The search was cancelled before completion.
+    Cancelled,
+    /// The search exceeded its time budget before completion.
+    Timeout,
 }
 
 impl ResolutionError {
@@ -34,7 +79,49 @@ impl ResolutionError {
     /// An error is definitive if the supplied recipes simply do not permit solving the problem, while it is not if
     /// there exists a possibility, however remote, that increasing the search space would allow finding a solution.
     pub fn is_definitive(&self) -> bool {
-        *self == Self::PreservationError
+        matches!(self, Self::PreservationError | Self::Infeasible)
+    }
+
+    /// Returns a configuration searching further than `current`, in the direction in which the search failed, if any.
+    ///
+    /// The bound hit is raised by 1, except for the number of recipes, which is raised by 4 as each additional recipe
+    /// is much cheaper to explore than each additional catalyst or repetition, and for the number of permutations of
+    /// catalysts, which is doubled as it grows combinatorially with the number of catalysts.
+    ///
+    /// On timeout, the time budget is doubled instead.
+    ///
+    /// Returns `None` if the error is definitive, if the search was cancelled, or if the bound is already at its maximum.
+    pub fn suggested_config(&self, current: SolverConfiguration) -> Option<SolverConfiguration> {
+        let mut suggested = current;
+
+        match self {
+            Self::PreservationError | Self::Infeasible | Self::Cancelled => return None,
+            Self::OutsideCatalysts { .. } => {
+                suggested.maximum_catalysts = current.maximum_catalysts.saturating_add(1);
+            }
+            Self::OutsidePermutations { .. } => {
+                suggested.maximum_catalyst_permutations = current.maximum_catalyst_permutations.saturating_mul(2);
+            }
+            Self::OutsideCount { .. } => {
+                suggested.maximum_repetitions = current.maximum_repetitions.saturating_add(1);
+            }
+            Self::OutsideRecipes { .. } => {
+                suggested.maximum_recipes = current.maximum_recipes.saturating_add(4);
+            }
+            Self::OutsideStages { .. } => {
+                suggested.maximum_stages = current.maximum_stages.saturating_add(1);
+            }
+            Self::Timeout => {
+                suggested.time_budget = current.time_budget.map(|budget| budget.saturating_mul(2));
+            }
+        }
+
+        (suggested != current).then_some(suggested)
+    }
+
+    //  Returns whether the search was interrupted, by cancellation or timeout, rather than failed.
+    fn is_interrupted(&self) -> bool {
+        matches!(self, Self::Cancelled | Self::Timeout)
     }
 }
 
@@ -59,6 +146,48 @@ pub struct SolverConfiguration {
     pub maximum_repetitions: u8,
     /// The maximum number of recipes in the path from source to target.
     pub maximum_recipes: u8,
+    /// The maximum number of stages in the path from source to target.
+    ///
+    /// The number of stages is only known once a path is found, hence paths with too many stages are found, then
+    /// discarded, rather than not explored.
+    pub maximum_stages: u8,
+    /// The increment of the bound on the number of recipes, for iterative deepening, or 0 to disable it.
+    ///
+    /// With iterative deepening, the search first starts with a bound of `deepening_step` recipes, and only the sets
+    /// of catalysts for which the bound was hit are searched again with a bound raised by `deepening_step`, and so on
+    /// until `maximum_recipes` is reached.
+    ///
+    /// As soon as a bound yields a solution, no deeper search is performed, hence deeper solutions with fewer stages are
+    /// not found.
+    pub deepening_step: u8,
+    /// Whether, among equally good paths, those with fewer distinct types of catalysts come first.
+    ///
+    /// This only affects the order of the paths, not which paths are retained.
+    pub prefer_fewer_catalyst_types: bool,
+    /// The maximum number of sets of catalysts generated for a given number of catalysts.
+    ///
+    /// The number of sets of `n` catalysts grows combinatorially with `n` and the number of arcospheres: once the
+    /// limit is exceeded, no greater number of catalysts is explored.
+    pub maximum_catalyst_permutations: usize,
+    /// Whether to explore all numbers of catalysts at once, rather than one at a time.
+    ///
+    /// The executor is then handed the searchers of all numbers of catalysts & counts at once, for better parallelism,
+    /// at the cost of exploring numbers of catalysts which the paths found then make unnecessary. The paths returned are
+    /// the same either way, except when stopping at the first path found, which always explores one at a time.
+    pub batch_catalysts: bool,
+    /// Whether to expand the forward & backward searches of each searcher concurrently, as per `Executor::join`.
+    ///
+    /// This only pays off with cores to spare, for example when searching with a few sets of catalysts, as otherwise the
+    /// cost of synchronizing both expansions outweighs their concurrency. The paths returned are the same either way.
+    pub concurrent_directions: bool,
+    /// The objective to optimize for, with regard to catalysts.
+    pub objective: Objective,
+    /// The maximum wall-clock duration of a search, if any.
+    ///
+    /// The budget is checked as often as the cancellation token, between the expansions of each searcher, hence a search
+    /// may slightly overrun its budget. Once exceeded, the search stops with `ResolutionError::Timeout`, see
+    /// `Solutions::best_so_far` to retrieve the paths found so far.
+    pub time_budget: Option<Duration>,
 }
 
 impl Default for SolverConfiguration {
@@ -69,6 +198,14 @@ impl Default for SolverConfiguration {
         let extra_catalysts = 1;
         let maximum_repetitions = 4;
         let maximum_recipes = 20;
+        let maximum_stages = u8::MAX;
+        let deepening_step = 0;
+        let prefer_fewer_catalyst_types = false;
+        let maximum_catalyst_permutations = 1 << 16;
+        let batch_catalysts = false;
+        let concurrent_directions = false;
+        let objective = Objective::default();
+        let time_budget = None;
 
         Self {
             maximum_catalysts,
@@ -76,12 +213,382 @@ impl Default for SolverConfiguration {
             extra_catalysts,
             maximum_repetitions,
             maximum_recipes,
+            maximum_stages,
+            deepening_step,
+            prefer_fewer_catalyst_types,
+            maximum_catalyst_permutations,
+            batch_catalysts,
+            concurrent_directions,
+            objective,
+            time_budget,
         }
     }
 }
 
-/// Solver.
+impl SolverConfiguration {
+    /// Creates a builder, starting from the default configuration.
+    pub fn builder() -> SolverConfigurationBuilder {
+        SolverConfigurationBuilder::default()
+    }
+
+    /// Creates a configuration with tight bounds, for interactive use.
+    ///
+    /// Simple transformations are solved near instantly, at the cost of failing to solve those requiring more than 2
+    /// catalysts, 2 repetitions, or 12 recipes.
+    pub fn quick() -> Self {
+        Self {
+            maximum_catalysts: 2,
+            extra_catalysts: 0,
+            maximum_repetitions: 2,
+            maximum_recipes: 12,
+            ..Self::default()
+        }
+    }
+
+    /// Creates a configuration with loose bounds, for offline use.
+    ///
+    /// Transformations well beyond those of Space Exploration may be solved, at the cost of much longer searches,
+    /// especially when no solution exists.
+    pub fn exhaustive() -> Self {
+        Self {
+            maximum_catalysts: 8,
+            extra_catalysts: 2,
+            maximum_repetitions: 8,
+            maximum_recipes: 32,
+            maximum_catalyst_permutations: 1 << 20,
+            ..Self::default()
+        }
+    }
+}
+
+/// Objective of the solver, with regard to catalysts.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Objective {
+    /// The paths with the fewest catalysts are best.
+    #[default]
+    FewestCatalysts,
+    /// The paths with the lowest total scarcity of catalysts are best, see `Solver::with_scarcity`.
+    ///
+    /// Only the paths found with the numbers of catalysts explored are considered, hence raising `extra_catalysts` may
+    /// be necessary to find paths with more, but less scarce, catalysts.
+    LowestScarcity,
+}
+
+/// Builder of `SolverConfiguration`, validating the configuration on build.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SolverConfigurationBuilder {
+    configuration: SolverConfiguration,
+}
+
+impl SolverConfigurationBuilder {
+    /// Sets the maximum number of catalysts to add.
+    pub fn maximum_catalysts(mut self, maximum: u8) -> Self {
+        self.configuration.maximum_catalysts = maximum;
+
+        self
+    }
+
+    /// Sets the minimum and maximum number of catalysts to add.
+    pub fn catalyst_range(mut self, minimum: u8, maximum: u8) -> Self {
+        self.configuration.minimum_catalysts = minimum;
+        self.configuration.maximum_catalysts = maximum;
+
+        self
+    }
+
+    /// Sets the maximum number of extra catalysts explored after first finding a solution.
+    pub fn extra_catalysts(mut self, extra: u8) -> Self {
+        self.configuration.extra_catalysts = extra;
+
+        self
+    }
+
+    /// Sets the maximum number of repetitions allowed.
+    pub fn maximum_repetitions(mut self, maximum: u8) -> Self {
+        self.configuration.maximum_repetitions = maximum;
+
+        self
+    }
+
+    /// Sets the maximum number of recipes in the path from source to target.
+    pub fn maximum_recipes(mut self, maximum: u8) -> Self {
+        self.configuration.maximum_recipes = maximum;
+
+        self
+    }
+
+    /// Sets the maximum number of stages in the path from source to target.
+    pub fn maximum_stages(mut self, maximum: u8) -> Self {
+        self.configuration.maximum_stages = maximum;
+
+        self
+    }
+
+    /// Sets the increment of the bound on the number of recipes, for iterative deepening, or 0 to disable it.
+    pub fn deepening_step(mut self, step: u8) -> Self {
+        self.configuration.deepening_step = step;
+
+        self
+    }
+
+    /// Sets whether, among equally good paths, those with fewer distinct types of catalysts come first.
+    pub fn prefer_fewer_catalyst_types(mut self, prefer: bool) -> Self {
+        self.configuration.prefer_fewer_catalyst_types = prefer;
+
+        self
+    }
+
+    /// Sets the maximum number of sets of catalysts generated for a given number of catalysts.
+    pub fn maximum_catalyst_permutations(mut self, maximum: usize) -> Self {
+        self.configuration.maximum_catalyst_permutations = maximum;
+
+        self
+    }
+
+    /// Sets whether to explore all numbers of catalysts at once, rather than one at a time.
+    pub fn batch_catalysts(mut self, batch: bool) -> Self {
+        self.configuration.batch_catalysts = batch;
+
+        self
+    }
+
+    /// Sets whether to expand the forward & backward searches of each searcher concurrently.
+    pub fn concurrent_directions(mut self, concurrent: bool) -> Self {
+        self.configuration.concurrent_directions = concurrent;
+
+        self
+    }
+
+    /// Sets the objective to optimize for, with regard to catalysts.
+    pub fn objective(mut self, objective: Objective) -> Self {
+        self.configuration.objective = objective;
+
+        self
+    }
+
+    /// Sets the maximum wall-clock duration of a search, if any.
+    pub fn time_budget(mut self, budget: Option<Duration>) -> Self {
+        self.configuration.time_budget = budget;
+
+        self
+    }
+
+    /// Builds the configuration, if valid.
+    pub fn build(self) -> Result<SolverConfiguration, ConfigurationError> {
+        let configuration = self.configuration;
+
+        if configuration.minimum_catalysts > configuration.maximum_catalysts {
+            return Err(ConfigurationError::InvertedCatalysts);
+        }
+
+        if configuration.maximum_repetitions == 0 {
+            return Err(ConfigurationError::NoRepetitions);
+        }
+
+        if configuration.maximum_stages == 0 {
+            return Err(ConfigurationError::NoStages);
+        }
+
+        Ok(configuration)
+    }
+}
+
+/// Error which may occur when building a configuration.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ConfigurationError {
+    /// The minimum number of catalysts exceeds the maximum number of catalysts.
+    InvertedCatalysts,
+    /// The maximum number of repetitions is 0, hence no count would ever be explored.
+    NoRepetitions,
+    /// The maximum number of stages is 0, hence no path would ever be found.
+    NoStages,
+}
+
+impl fmt::Display for ConfigurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{self:?}")
+    }
+}
+
+impl error::Error for ConfigurationError {}
+
+/// Statistics of a search.
+///
+/// The statistics are aggregated across all the searches performed, for all numbers of catalysts and repetitions.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SolveStats {
+    /// The number of sets expanded by the forward searches.
+    pub forward_expanded: u64,
+    /// The number of sets expanded by the backward searches.
+    pub backward_expanded: u64,
+    /// The number of sets of catalysts tried.
+    pub catalysts_tried: u64,
+    /// The number of count levels tried, that is the number of (number of catalysts, count) pairs.
+    pub counts_tried: u64,
+    /// The peak number of sets known by any single forward search.
+    pub peak_forward: usize,
+    /// The peak number of sets known by any single backward search.
+    pub peak_backward: usize,
+}
+
+impl SolveStats {
+    /// Merges the statistics of `other` into `self`.
+    pub fn merge(&mut self, other: &Self) {
+        self.forward_expanded += other.forward_expanded;
+        self.backward_expanded += other.backward_expanded;
+        self.catalysts_tried += other.catalysts_tried;
+        self.counts_tried += other.counts_tried;
+        self.peak_forward = cmp::max(self.peak_forward, other.peak_forward);
+        self.peak_backward = cmp::max(self.peak_backward, other.peak_backward);
+    }
+}
+
+/// Cancellation token of the solver.
+///
+/// The token may be cloned and handed over to another thread, which may then cancel the search at any point. The
+/// solver only checks the token periodically, hence cancellation is not immediate.
 #[derive(Clone, Debug, Default)]
+pub struct Cancellation(Arc<AtomicBool>);
+
+impl Cancellation {
+    /// Creates a new, uncancelled, token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests the cancellation of the search(es) using this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether cancellation was requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Cache of the solutions of the solver.
+///
+/// The solutions are keyed on all the inputs of the solver, see `SolveKey`, hence a cache may be shared between
+/// solvers, whatever their configuration, cost & scarcity functions, or allowed & forbidden recipes.
+pub trait SolveCache<F>: fmt::Debug + Send + Sync
+where
+    F: ArcosphereFamily,
+{
+    /// Returns the cached solutions for `key`, if any.
+    fn get(&self, key: &SolveKey<F>) -> Option<Vec<StagedPath<F>>>;
+
+    /// Caches the solutions for `key`.
+    fn put(&self, key: SolveKey<F>, paths: Vec<StagedPath<F>>);
+}
+
+impl<F, C> SolveCache<F> for Arc<C>
+where
+    F: ArcosphereFamily,
+    C: SolveCache<F>,
+{
+    fn get(&self, key: &SolveKey<F>) -> Option<Vec<StagedPath<F>>> {
+        (**self).get(key)
+    }
+
+    fn put(&self, key: SolveKey<F>, paths: Vec<StagedPath<F>>) {
+        (**self).put(key, paths)
+    }
+}
+
+/// Key of the solutions of the solver, in a `SolveCache`.
+///
+/// The cost & scarcity functions are captured by their values, hence two solvers whose functions agree on every recipe
+/// & arcosphere share their entries.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SolveKey<F>
+where
+    F: ArcosphereFamily,
+{
+    /// The source of the solve.
+    pub source: F::Set,
+    /// The target of the solve.
+    pub target: F::Set,
+    /// The configuration of the solver.
+    pub configuration: SolverConfiguration,
+    /// The recipes available to the solver, that is allowed and not forbidden, in order.
+    pub recipes: Vec<F::Recipe>,
+    /// The cost of each of `recipes`, if the solver has a cost function.
+    pub costs: Option<Vec<u32>>,
+    /// The scarcity of each arcosphere, in order of index.
+    pub scarcities: Vec<u32>,
+}
+
+/// In-memory cache of the solutions of the solver, never evicting any entry.
+pub struct MemoryCache<F>
+where
+    F: ArcosphereFamily,
+{
+    entries: Mutex<CacheEntries<F>>,
+}
+
+impl<F> MemoryCache<F>
+where
+    F: ArcosphereFamily,
+{
+    /// Creates a new, empty, cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of entries in the cache.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheEntries<F>> {
+        //  Insertions & lookups cannot panic midway, hence the map is consistent even if the lock is poisoned.
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<F> Default for MemoryCache<F>
+where
+    F: ArcosphereFamily,
+{
+    fn default() -> Self {
+        let entries = Mutex::default();
+
+        Self { entries }
+    }
+}
+
+impl<F> fmt::Debug for MemoryCache<F>
+where
+    F: ArcosphereFamily,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("MemoryCache").field("len", &self.len()).finish()
+    }
+}
+
+impl<F> SolveCache<F> for MemoryCache<F>
+where
+    F: ArcosphereFamily<Set: Send, Recipe: Send>,
+{
+    fn get(&self, key: &SolveKey<F>) -> Option<Vec<StagedPath<F>>> {
+        self.lock().get(key).cloned()
+    }
+
+    fn put(&self, key: SolveKey<F>, paths: Vec<StagedPath<F>>) {
+        self.lock().insert(key, paths);
+    }
+}
+
+type CacheEntries<F> = HashMap<SolveKey<F>, Vec<StagedPath<F>>>;
+
+/// Solver.
+#[derive(Clone, Debug)]
 pub struct Solver<F, E>
 where
     F: ArcosphereFamily,
@@ -89,6 +596,12 @@ where
     family: F,
     executor: E,
     configuration: SolverConfiguration,
+    cancellation: Option<Cancellation>,
+    cost: Option<fn(&F::Recipe) -> u32>,
+    scarcity: Option<fn(F::Arcosphere) -> u32>,
+    allowed: Option<Vec<F::Recipe>>,
+    forbidden: Vec<F::Recipe>,
+    cache: Option<Arc<dyn SolveCache<F>>>,
 }
 
 //
@@ -104,13 +617,27 @@ where
     where
         E: Default,
     {
+        debug_assert_eq!(Ok(()), F::validate());
+
         let executor = E::default();
         let configuration = SolverConfiguration::default();
+        let cancellation = None;
+        let cost = None;
+        let scarcity = None;
+        let allowed = None;
+        let forbidden = Vec::new();
+        let cache = None;
 
         Self {
             family,
             executor,
             configuration,
+            cancellation,
+            cost,
+            scarcity,
+            allowed,
+            forbidden,
+            cache,
         }
     }
 
@@ -121,18 +648,140 @@ where
         self
     }
 
+    /// Sets the cancellation token.
+    ///
+    /// Once the token is cancelled, any on-going search returns `ResolutionError::Cancelled`.
+    pub fn with_cancellation(mut self, cancellation: Cancellation) -> Self {
+        self.cancellation = Some(cancellation);
+
+        self
+    }
+
+    /// Sets the cost function of recipes.
+    ///
+    /// When set, the solver returns the paths with the minimum total cost -- the sum of the cost of their recipes --
+    /// rather than the paths with the minimum number of stages & recipes.
+    pub fn with_cost(mut self, cost: fn(&F::Recipe) -> u32) -> Self {
+        self.cost = Some(cost);
+
+        self
+    }
+
+    /// Sets the scarcity function of arcospheres.
+    ///
+    /// With `Objective::LowestScarcity`, the solver returns the paths with the minimum total scarcity of catalysts --
+    /// the sum of the scarcity of each catalyst -- first. Without a scarcity function, each catalyst has a scarcity of 1.
+    pub fn with_scarcity(mut self, scarcity: fn(F::Arcosphere) -> u32) -> Self {
+        self.scarcity = Some(scarcity);
+
+        self
+    }
+
+    /// Sets the allowed recipes, or allows all recipes if `None`.
+    ///
+    /// The solver only ever uses allowed recipes, and reports `ResolutionError::OutsideRecipes` if they alone prevent
+    /// the change of polarity. A recipe both allowed and forbidden is forbidden.
+    pub fn with_allowed_recipes(mut self, allowed: Option<&[F::Recipe]>) -> Self {
+        self.allowed = allowed.map(<[F::Recipe]>::to_vec);
+
+        self
+    }
+
+    /// Sets the forbidden recipes.
+    ///
+    /// The solver never uses a forbidden recipe, and reports `ResolutionError::OutsideRecipes` if forbidding them alone
+    /// prevents the change of polarity.
+    pub fn with_forbidden_recipes<I>(mut self, forbidden: I) -> Self
+    where
+        I: IntoIterator<Item = F::Recipe>,
+    {
+        self.forbidden = forbidden.into_iter().collect();
+
+        self
+    }
+
+    /// Sets the cache of solutions.
+    ///
+    /// When set, `solve` first looks up the solutions in the cache, and only searches on a miss, caching the solutions
+    /// found. Errors are not cached. A cache may be shared, even between solvers set up differently, by wrapping it in
+    /// an `Arc`.
+    pub fn with_cache<C>(mut self, cache: C) -> Self
+    where
+        C: SolveCache<F> + 'static,
+    {
+        self.cache = Some(Arc::new(cache));
+
+        self
+    }
+
     /// Sets the executor.
     pub fn with_executor<OE>(self, executor: OE) -> Solver<F, OE> {
         let Solver {
-            family, configuration, ..
+            family,
+            configuration,
+            cancellation,
+            cost,
+            scarcity,
+            allowed,
+            forbidden,
+            cache,
+            ..
         } = self;
 
         Solver {
             family,
             executor,
             configuration,
+            cancellation,
+            cost,
+            scarcity,
+            allowed,
+            forbidden,
+            cache,
+        }
+    }
+
+    //  Returns the allowed, and not forbidden, recipes.
+    fn recipes(&self) -> impl Iterator<Item = F::Recipe> + use<'_, F, E> {
+        let is_allowed = |recipe: &F::Recipe| self.allowed.as_ref().is_none_or(|allowed| allowed.contains(recipe));
+
+        F::Recipe::all().filter(move |recipe| is_allowed(recipe) && !self.forbidden.contains(recipe))
+    }
+
+    //  Returns the key of the solutions for `source` and `target`, in the cache.
+    fn cache_key(&self, source: F::Set, target: F::Set) -> SolveKey<F> {
+        let recipes: Vec<_> = self.recipes().collect();
+        let costs = self.cost.map(|cost| recipes.iter().map(cost).collect());
+
+        let scarcity = self.scarcity.unwrap_or(|_| 1);
+        let scarcities = (0..F::Arcosphere::DIMENSION)
+            .map(|index| scarcity(F::Arcosphere::from_index(index)))
+            .collect();
+
+        SolveKey {
+            source,
+            target,
+            configuration: self.configuration,
+            recipes,
+            costs,
+            scarcities,
         }
     }
+
+    //  Returns whether the recipes are restricted, in any way.
+    fn is_restricted(&self) -> bool {
+        self.allowed.is_some() || !self.forbidden.is_empty()
+    }
+}
+
+impl<F, E> Default for Solver<F, E>
+where
+    F: ArcosphereFamily + Default,
+    E: Default,
+{
+    fn default() -> Self {
+        Self::new(F::default())
+    }
 }
 
 //
@@ -158,7 +807,7 @@ impl<E> SeSolver<E> {
 
 impl<F, E> Solver<F, E>
 where
-    F: ArcosphereFamily<Arcosphere: Send, Set: Send, Recipe: Send> + Send,
+    F: ArcosphereFamily<Arcosphere: Send, Set: Send, Recipe: Send + Sync> + Send,
     E: Executor,
 {
     /// Looks for all possible recipe paths from `source` to `target` with a minimum number of catalysts.
@@ -169,831 +818,3556 @@ where
     /// If the solver does not return any solution, then raising either the number of catalysts or the number of recipes
     /// may allow it to find further solutions.
     pub fn solve(&self, source: F::Set, target: F::Set) -> Result<Vec<StagedPath<F>>, ResolutionError> {
-        //  Special case: impossible.
+        let Some(cache) = &self.cache else {
+            return self.solve_with_stats(source, target).map(|(paths, _)| paths);
+        };
 
-        if source.len() != target.len() {
-            return Err(ResolutionError::PreservationError);
+        let key = self.cache_key(source, target);
+
+        if let Some(paths) = cache.get(&key) {
+            return Ok(paths);
         }
 
-        //  Special case: 0 conversion.
+        let (paths, _) = self.solve_with_stats(source, target)?;
 
-        if source == target {
-            let path = Path {
-                source,
-                target,
-                count: ONE,
-                catalysts: F::Set::default(),
-                recipes: Vec::new(),
-            };
+        cache.put(key, paths.clone());
 
-            return Ok(vec![StagedPath::parallelize(path)]);
-        }
+        Ok(paths)
+    }
 
-        //  Special case: 1 conversion.
+    /// Looks for all possible recipe paths from `source` to `target` with a minimum number of catalysts, returning
+    /// statistics about the search alongside.
+    ///
+    /// See `solve` for details.
+    pub fn solve_with_stats(
+        &self,
+        source: F::Set,
+        target: F::Set,
+    ) -> Result<(Vec<StagedPath<F>>, SolveStats), ResolutionError> {
+        let mut solutions = self.solve_iter(source, target);
 
-        for recipe in (0..F::Recipe::DIMENSION).map(F::Recipe::from_index) {
-            if source != recipe.input() || target != recipe.output() {
-                continue;
-            }
+        let paths = solutions.best()?;
 
-            let path = Path {
-                source,
-                target,
-                count: ONE,
-                catalysts: F::Set::default(),
-                recipes: vec![recipe],
-            };
+        Ok((paths, solutions.stats()))
+    }
 
-            return Ok(vec![StagedPath::parallelize(path)]);
-        }
+    /// Looks for all possible recipe paths from `source` to `target` with a minimum number of catalysts, returning the
+    /// midpoint of each path alongside.
+    ///
+    /// See `solve` for details, and `Solutions::midpoint` for the definition of midpoint.
+    #[allow(clippy::type_complexity)]
+    pub fn solve_with_midpoints(
+        &self,
+        source: F::Set,
+        target: F::Set,
+    ) -> Result<Vec<(StagedPath<F>, Option<F::Set>)>, ResolutionError> {
+        let mut solutions = self.solve_iter(source, target);
 
-        //  Is an inversion required, or not?
+        let paths = solutions.best()?;
 
-        self.explore_catalysts_space(source, target)
+        Ok(paths
+            .into_iter()
+            .map(|path| {
+                let midpoint = solutions.midpoint(&path);
+
+                (path, midpoint)
+            })
+            .collect())
     }
-}
 
-//
-//  Implementation
-//
+    /// Looks for any recipe path from `source` to `target`, returning the first path found.
+    ///
+    /// The catalysts space is explored from the minimum number of catalysts onward, as with `solve`, but the search
+    /// stops as soon as any path is found: the path is valid, and uses a minimum number of catalysts, but may not be the
+    /// shortest.
+    pub fn solve_one(&self, source: F::Set, target: F::Set) -> Result<StagedPath<F>, ResolutionError> {
+        let mut solutions = self.solve_iter(source, target).with_first();
+
+        solutions
+            .next()
+            .ok_or_else(|| solutions.error().unwrap_or_else(|| solutions.exhausted_error()))
+    }
 
-const ONE: NonZeroU8 = NonZeroU8::new(1).unwrap();
+    /// Looks for all possible recipe paths from `source` to `target`, including longer ones, sorted from best to worst.
+    ///
+    /// Unlike `solve`, the paths are not filtered, and thus include all paths found with the number of catalysts
+    /// explored, ie the minimum number of catalysts up to `extra_catalysts` more. See `Solutions::best` for the
+    /// definition of best.
+    pub fn solve_all(&self, source: F::Set, target: F::Set) -> Result<Vec<StagedPath<F>>, ResolutionError> {
+        self.solve_iter(source, target).all()
+    }
 
-impl SolverConfiguration {
-    fn catalysts(&self) -> Range<usize> {
-        let start = self.minimum_catalysts as usize;
-        let end = self.maximum_catalysts as usize + 1;
+    /// Looks for all possible recipe paths from `source` to `target` using exactly `catalysts` as catalysts.
+    ///
+    /// Only the count space is explored, which is much faster than exploring the catalysts space when the catalysts
+    /// are known ahead of time. See `solve` for details.
+    pub fn solve_with_catalysts(
+        &self,
+        source: F::Set,
+        target: F::Set,
+        catalysts: F::Set,
+    ) -> Result<Vec<StagedPath<F>>, ResolutionError> {
+        self.solve_with_catalysts_until(source, target, catalysts, self.configuration.deadline())
+    }
 
-        start..end
+    /// Looks for all possible recipe paths from `source` to `target` using a subset of `available` as catalysts, with a
+    /// minimum number of catalysts.
+    ///
+    /// Only the subsets of `available` are explored, by increasing number of catalysts, as `solve` explores all sets of
+    /// catalysts. Paths requiring any catalyst outside `available` are never returned. See `solve` for details.
+    pub fn solve_with_available_catalysts(
+        &self,
+        source: F::Set,
+        target: F::Set,
+        available: F::Set,
+    ) -> Result<Vec<StagedPath<F>>, ResolutionError> {
+        self.solve_iter_with_available_catalysts(source, target, available)
+            .best()
     }
 
-    fn repetitions(&self) -> Range<u8> {
-        let end = self.maximum_repetitions + 1;
+    /// Looks for all possible recipe paths from `source` to a superset of `target`, with a minimum number of catalysts.
+    ///
+    /// The leftover spheres, if any, are part of the target of the resulting paths: that is, the target of each path is
+    /// `target` plus its leftovers. See `solve` for details.
+    pub fn solve_superset(&self, source: F::Set, target: F::Set) -> Result<Vec<StagedPath<F>>, ResolutionError> {
+        let Some(surplus) = source.len().checked_sub(target.len()) else {
+            return Err(ResolutionError::PreservationError);
+        };
 
-        1..end
-    }
-}
+        let limit = self.configuration.maximum_catalyst_permutations;
 
-impl<F, E> Solver<F, E>
-where
-    F: ArcosphereFamily<Arcosphere: Send, Set: Send, Recipe: Send> + Send,
-    E: Executor,
-{
-    fn explore_catalysts_space(&self, source: F::Set, target: F::Set) -> Result<Vec<StagedPath<F>>, ResolutionError> {
-        let catalysts = self.configuration.catalysts();
+        //  The backward search requires an exact target, hence every possible surplus is tried in turn.
+        let surpluses = if surplus == 0 {
+            vec![F::Set::default()]
+        } else {
+            Searcher::<F>::generate_catalysts(surplus, limit).ok_or(ResolutionError::OutsidePermutations {
+                maximum_catalyst_permutations: limit,
+            })?
+        };
 
-        let mut maximum_catalysts = catalysts.end - 1;
+        //  Hitting the bound on the number of recipes is the more actionable error, then the bound on the count.
+        let actionable = |error: &ResolutionError| match error {
+            ResolutionError::OutsideRecipes { .. } => 2,
+            ResolutionError::OutsideCount { .. } => 1,
+            _ => 0,
+        };
 
-        let mut results = FxHashSet::default();
-        let mut last_error = None;
+        //  The budget is shared by all surpluses.
+        let deadline = self.configuration.deadline();
+
+        let mut searches: Vec<_> = surpluses
+            .into_iter()
+            .map(|surplus| self.solve_iter(source, target + surplus).with_deadline(deadline))
+            .collect();
 
-        for i in catalysts {
-            if i > maximum_catalysts {
+        let mut results = Vec::new();
+        let mut maximum = usize::MAX;
+
+        //  The surpluses are explored one number of catalysts at a time, all of them in turn, so that no surplus is
+        //  explored past the minimum number of catalysts of any surplus, plus the extra catalysts.
+        for i in self.configuration.catalysts() {
+            if i > maximum {
                 break;
             }
 
-            let result = self.explore_count_space(i, source, target);
+            for solutions in &mut searches {
+                self.check_interruption(deadline)?;
+
+                while let Some(path) = solutions.next_until(i) {
+                    results.push(path);
+                }
 
-            match result {
-                Ok(paths) => results.extend(paths),
-                Err(e) if e.is_definitive() => return Err(e),
-                Err(e) if e == ResolutionError::OutsideCount => last_error = Some(e),
-                _ => (),
+                if let Some(error) = solutions.error.filter(ResolutionError::is_interrupted) {
+                    return Err(error);
+                }
             }
 
-            if !results.is_empty() {
-                maximum_catalysts = cmp::min(maximum_catalysts, i + self.configuration.extra_catalysts as usize);
+            if let Some(minimum) = results.iter().map(|p| p.path.catalysts.len()).min() {
+                maximum = cmp::min(maximum, minimum + self.configuration.extra_catalysts as usize);
             }
         }
 
-        let mut results: Vec<_> = results.into_iter().collect();
+        let Some(minimum) = results.iter().map(|p| p.path.catalysts.len()).min() else {
+            let error = searches
+                .iter()
+                .filter_map(Solutions::error)
+                .max_by_key(actionable)
+                .unwrap_or(self.configuration.outside_catalysts());
 
-        let Some(shortest) = results.iter().map(|p| (p.stages.len(), p.path.recipes.len())).min() else {
-            //  Didn't find anything, it may be necessary to raise the number of catalysts or the number of recipes in a
-            //  path.
-            return Err(last_error.unwrap_or(ResolutionError::OutsideCatalysts));
+            return Err(error);
         };
 
-        //  Should longer paths still be made available?
-        results.retain(|p| (p.stages.len(), p.path.recipes.len()) == shortest);
+        //  Each surplus is solved independently, hence the minimum number of catalysts must be enforced across all.
+        let maximum = minimum + self.configuration.extra_catalysts as usize;
 
-        //  Stable output is nice, and definitely not the most costly part anyway...
-        results.sort_unstable();
+        results.retain(|p| p.path.catalysts.len() <= maximum);
 
-        Ok(results)
+        Ok(self.retain_best(results))
     }
 
-    fn explore_count_space(
+    /// Returns all the sets reachable from `source`, including `source` itself, within the maximum number of recipes.
+    ///
+    /// As in `solve`, each set of catalysts within the range of number of catalysts is added to `source`, and must be
+    /// returned as is, up to the maximum number of permutations of catalysts. Only a count of 1 is explored.
+    pub fn reachable(&self, source: F::Set) -> HashSet<F::Set> {
+        let family = self.family;
+        let recipes: Vec<_> = self.recipes().collect();
+        let maximum_recipes = self.configuration.maximum_recipes;
+        let limit = self.configuration.maximum_catalyst_permutations;
+
+        //  Greater numbers of catalysts would only generate more permutations, hence the exploration stops there.
+        let candidates = self
+            .configuration
+            .catalysts()
+            .map_while(|number| match number {
+                0 => Some(vec![F::Set::default()]),
+                _ => Searcher::<F>::generate_catalysts(number, limit),
+            })
+            .flatten();
+
+        let tasks: Vec<_> = candidates
+            .filter_map(|catalysts| {
+                let start = source.checked_add(catalysts)?;
+                let recipes = &recipes;
+
+                Some(move || (catalysts, Self::reach(family, start, recipes, maximum_recipes)))
+            })
+            .collect();
+
+        let mut reachable = HashSet::from_iter([source]);
+
+        for (catalysts, reached) in self.executor.execute(tasks) {
+            reachable.extend(reached.into_iter().filter_map(|set| set.checked_sub(catalysts)));
+        }
+
+        reachable
+    }
+
+    /// Looks for the shortest recipe path from `count` times `source` to any set satisfying `predicate`.
+    ///
+    /// Without a concrete target, only the forward search is possible, within the maximum number of recipes, and no
+    /// catalyst is added. The set reached need not be a multiple of `count`, hence the path found goes from
+    /// `source * count` to the set reached, with a count of 1.
+    pub fn solve_until<P>(
         &self,
-        catalysts: usize,
         source: F::Set,
-        target: F::Set,
-    ) -> Result<FxHashSet<StagedPath<F>>, ResolutionError> {
-        let configuration = self.configuration.into();
-        let repetitions = self.configuration.repetitions();
+        count: NonZeroU8,
+        predicate: P,
+    ) -> Result<StagedPath<F>, ResolutionError>
+    where
+        P: Fn(&F::Set) -> bool,
+    {
+        use searcher::DirectionSearcher;
 
-        let mut last_error = None;
+        let start = source * count;
 
-        for count in repetitions {
-            let Some(count) = NonZeroU8::new(count) else {
-                continue;
+        let staged = |target, recipes| {
+            let path = Path {
+                source: start,
+                target,
+                count: ONE,
+                catalysts: F::Set::default(),
+                recipes,
             };
 
-            let searchers = Searcher::generate_searchers(self.family, source, target, count, catalysts, configuration);
+            StagedPath::parallelize(path)
+        };
+
+        if predicate(&start) {
+            return Ok(staged(start, Vec::new()));
+        }
 
-            let tasks: Vec<_> = searchers.into_iter().map(|searcher| move || searcher.solve()).collect();
+        let recipes: Vec<_> = self.recipes().collect();
 
-            let mut results = FxHashSet::default();
+        let searcher = searcher::ForwardSearcher::new(self.family, &recipes);
 
-            for result in self.executor.execute(tasks) {
-                match result {
-                    Ok(paths) => results.extend(paths),
-                    Err(e) if e.is_definitive() => return Err(e),
-                    Err(e) if e == ResolutionError::OutsideRecipes => last_error = Some(e),
-                    _ => (),
-                }
+        let mut known = HashMap::default();
+        let mut inputs = HashSet::from_iter([start]);
+        let mut outputs = HashMap::default();
+
+        let deadline = self.configuration.deadline();
+
+        for _ in 0..self.configuration.maximum_recipes {
+            if inputs.is_empty() {
+                break;
             }
 
-            if results.is_empty() {
-                continue;
+            self.check_interruption(deadline)?;
+
+            searcher.fold(start, &known, &inputs, &mut outputs);
+
+            known.extend(outputs.iter().map(|(&key, &value)| (key, value)));
+
+            //  Several sets may satisfy the predicate at the same depth, the lowest is retained for reproducibility.
+            if let Some(&reached) = outputs.keys().filter(|set| predicate(set)).min() {
+                let mut recipes = Vec::new();
+
+                Searcher::<F>::stitch_forward(start, &known, reached, &mut recipes);
+
+                return Ok(staged(reached, recipes));
             }
 
-            return Ok(results);
+            inputs.clear();
+            inputs.extend(outputs.keys().copied());
         }
 
-        //  Didn't find anything, it may be necessary to raise the number of catalysts or the number of recipes in a
-        //  path.
-        Err(last_error.unwrap_or(ResolutionError::OutsideCount))
+        Err(self.configuration.outside_recipes())
     }
-}
 
-#[derive(Clone, Copy, Debug)]
-struct SearcherConfiguration {
-    maximum_recipes: u8,
-}
+    /// Looks for all possible recipe paths from `source` to `target`, lazily.
+    ///
+    /// The paths are yielded in discovery order, as each number of catalysts is explored. Unlike `solve`, the paths are
+    /// not filtered, and thus a path with more catalysts may be longer than a path with fewer. See `Solutions::best`
+    /// to only retain the best paths.
+    pub fn solve_iter(&self, source: F::Set, target: F::Set) -> Solutions<'_, F, E> {
+        //  Special case: impossible.
 
-impl From<SolverConfiguration> for SearcherConfiguration {
-    fn from(value: SolverConfiguration) -> SearcherConfiguration {
-        let SolverConfiguration { maximum_recipes, .. } = value;
+        if source.len() != target.len() {
+            return Solutions::failed(self, ResolutionError::PreservationError);
+        }
 
-        SearcherConfiguration { maximum_recipes }
-    }
-}
+        //  Special case: no combination of recipes, whatever the catalysts.
 
-struct Searcher<F>
-where
-    F: ArcosphereFamily,
-{
-    family: F,
-    source: F::Set,
-    target: F::Set,
-    count: NonZeroU8,
-    catalysts: F::Set,
-    configuration: SearcherConfiguration,
-}
+        if !self.is_feasible(source, target) {
+            return Solutions::failed(self, ResolutionError::Infeasible);
+        }
 
-impl<F> Searcher<F>
-where
-    F: ArcosphereFamily,
-{
-    fn generate_searchers(
-        family: F,
-        source: F::Set,
-        target: F::Set,
-        count: NonZeroU8,
-        number_catalysts: usize,
-        configuration: SearcherConfiguration,
-    ) -> Vec<Searcher<F>> {
-        let catalysts = Self::generate_catalysts(number_catalysts);
+        //  Special case: the restriction of recipes prevents the change of polarity, whatever the count.
 
-        catalysts
-            .into_iter()
-            .map(|catalysts| Searcher {
-                family,
+        if self.is_polarity_restricted(source, target) {
+            return Solutions::failed(self, self.configuration.outside_recipes());
+        }
+
+        //  Special case: 0 conversion.
+
+        if source == target {
+            let path = Path {
                 source,
                 target,
-                count,
-                catalysts,
-                configuration,
-            })
-            .collect()
-    }
-
-    //  Generates all permutations of `number` spheres.
-    //
-    //  The generated number of permutations is "triangularly" quadratic:
-    //
-    //  -   0: 0.
-    //  -   1: 8, one of each.
-    //  -   2: 36, at each level 8, then 7, then 6, etc...
-    //  -   ...
-    fn generate_catalysts(number: usize) -> Vec<F::Set> {
-        let mut result = Vec::new();
+                count: ONE,
+                catalysts: F::Set::default(),
+                recipes: Vec::new(),
+            };
 
-        if number == 0 {
-            return result;
+            return Solutions::trivial(self, StagedPath::parallelize(path));
         }
 
-        Self::generate_catalysts_rec(F::Set::default(), number, &mut result);
+        //  Special case: 1 conversion.
 
-        result
-    }
+        for recipe in self.recipes() {
+            if source != recipe.input() || target != recipe.output() {
+                continue;
+            }
 
-    fn generate_catalysts_rec(catalysts: F::Set, number: usize, output: &mut Vec<F::Set>) {
-        debug_assert!(number > 0);
+            let path = Path {
+                source,
+                target,
+                count: ONE,
+                catalysts: F::Set::default(),
+                recipes: vec![recipe],
+            };
 
-        //  Do not insert spheres with a lower index than the highest index sphere used: it only creates duplicates.
-        let minimum = catalysts
-            .into_iter()
-            .last()
-            .map(|sphere| sphere.into_index())
-            .unwrap_or_default();
+            return Solutions::trivial(self, StagedPath::parallelize(path));
+        }
 
-        let generator = (minimum..F::Arcosphere::DIMENSION).map(|i| {
-            let mut catalysts = catalysts;
+        //  Is an inversion required, or not?
 
-            catalysts.insert(F::Arcosphere::from_index(i));
+        Solutions::new(self, source, target)
+    }
 
-            catalysts
-        });
+    /// Looks for all possible recipe paths from `source` to `target` using a subset of `available` as catalysts, lazily.
+    ///
+    /// See `solve_iter` and `solve_with_available_catalysts` for details.
+    pub fn solve_iter_with_available_catalysts(
+        &self,
+        source: F::Set,
+        target: F::Set,
+        available: F::Set,
+    ) -> Solutions<'_, F, E> {
+        self.solve_iter(source, target).with_available_catalysts(available)
+    }
 
-        if number == 1 {
-            output.extend(generator);
-            return;
-        }
+    /// Looks for all possible recipe paths from `source` to `target` with a minimum number of catalysts, keeping the
+    /// state of the search on failure.
+    ///
+    /// The state may be fed back to `resume`, typically with a greater maximum number of recipes, to continue the
+    /// search rather than start it from scratch. See `solve` for details.
+    pub fn solve_resumable(
+        &self,
+        source: F::Set,
+        target: F::Set,
+    ) -> Result<Vec<StagedPath<F>>, (ResolutionError, SearchState<F>)> {
+        let mut solutions = self.solve_iter(source, target).with_suspended();
 
-        for catalysts in generator {
-            Self::generate_catalysts_rec(catalysts, number - 1, output);
-        }
+        solutions.best().map_err(|error| {
+            let levels = solutions.suspended.take().unwrap_or_default();
+
+            (error, SearchState { levels })
+        })
     }
-}
 
-impl<F> Searcher<F>
-where
-    F: ArcosphereFamily,
-{
-    fn solve(&self) -> Result<FxHashSet<StagedPath<F>>, ResolutionError> {
-        let maximum_iterations = (self.configuration.maximum_recipes as usize + 1) / 2;
+    /// Resumes a search, from the state of a previous search.
+    ///
+    /// Only the searches which hit the maximum number of recipes are resumed, with the current maximum number of
+    /// recipes. The number of catalysts and repetitions explored are those of the original search, regardless of the
+    /// current configuration.
+    pub fn resume(&self, state: SearchState<F>) -> Result<Vec<StagedPath<F>>, (ResolutionError, SearchState<F>)> {
+        let recipes: Vec<_> = self.recipes().collect();
+        let extra_catalysts = self.configuration.extra_catalysts as usize;
 
-        let source = self.source * self.count + self.catalysts;
-        let target = self.target * self.count + self.catalysts;
+        let mut stats = SolveStats::default();
+        let mut results = HashSet::default();
+        let mut remaining = Vec::new();
 
-        let mut forward = FxHashMap::default();
-        let mut backward = FxHashMap::default();
+        let deadline = self.configuration.deadline();
 
-        let mut in_forward = FxHashSet::from_iter([source]);
-        let mut in_backward = FxHashSet::from_iter([target]);
+        //  The number of catalysts of the first solution, and of the last solution.
+        let mut first = None;
+        let mut last = None;
 
-        let mut out_forward = FxHashMap::default();
-        let mut out_backward = FxHashMap::default();
+        let mut levels = state.levels.into_iter();
 
-        for _ in 0..maximum_iterations {
-            if in_forward.is_empty() && in_backward.is_empty() {
-                return Err(ResolutionError::OutsideCatalysts);
+        while let Some(level) = levels.next() {
+            if first.is_some_and(|first| level.catalysts > first + extra_catalysts) {
+                break;
             }
 
-            let searcher = searcher::ForwardSearcher::new(self.family);
+            //  Only the lowest count matters, for a given number of catalysts.
+            if last == Some(level.catalysts) {
+                continue;
+            }
 
-            let matched = Self::advance(
-                &searcher,
-                source,
-                &mut forward,
-                &mut in_forward,
-                &mut out_forward,
-                &backward,
-            );
+            let mut batch = BatchLevel::new(level);
 
-            if matched {
-                return Ok(self.stitch(&forward, &backward, out_forward.keys().copied()));
-            }
+            self.explore_levels(slice::from_mut(&mut batch), &recipes, &mut stats, false, deadline);
 
-            let searcher = searcher::BackwardSearcher::new(self.family);
+            let BatchLevel {
+                level, paths, error, ..
+            } = batch;
 
-            let matched = Self::advance(
-                &searcher,
-                target,
-                &mut backward,
-                &mut in_backward,
-                &mut out_backward,
-                &forward,
-            );
+            if let Some(error) = error {
+                remaining.push(level);
+                remaining.extend(levels);
 
-            if matched {
-                return Ok(self.stitch(&forward, &backward, out_backward.keys().copied()));
+                return Err((error, SearchState { levels: remaining }));
             }
-        }
 
-        if in_forward.is_empty() && in_backward.is_empty() {
-            return Err(ResolutionError::OutsideCatalysts);
-        }
+            if paths.is_empty() {
+                if !level.searchers.is_empty() {
+                    remaining.push(level);
+                }
 
-        Err(ResolutionError::OutsideRecipes)
-    }
+                continue;
+            }
 
-    //  Returns true if a connection has been found.
-    fn advance<S, OF>(
-        searcher: &S,
-        start: F::Set,
-        known: &mut FxHashMap<F::Set, S::Recipe>,
-        inputs: &mut FxHashSet<F::Set>,
-        outputs: &mut FxHashMap<F::Set, S::Recipe>,
-        opposite_known: &FxHashMap<F::Set, OF>,
-    ) -> bool
-    where
-        S: searcher::DirectionSearcher<Set = F::Set>,
-    {
-        searcher.fold(start, known, inputs, outputs);
+            first.get_or_insert(level.catalysts);
+            last = Some(level.catalysts);
 
-        inputs.clear();
-        inputs.extend(outputs.keys().copied());
+            results.extend(paths.into_keys());
+        }
 
-        known.extend(outputs.iter().map(|(&key, &value)| (key, value)));
+        if results.is_empty() {
+            let error = if remaining.is_empty() {
+                self.configuration.outside_catalysts()
+            } else {
+                self.configuration.outside_recipes()
+            };
 
-        outputs.keys().any(|key| opposite_known.contains_key(key))
+            return Err((error, SearchState { levels: remaining }));
+        }
+
+        Ok(self.retain_best(results.into_iter().collect()))
     }
+}
 
-    fn stitch<C>(
-        &self,
-        forward: &FxHashMap<F::Set, F::Recipe>,
-        backward: &FxHashMap<F::Set, Reverse<F::Recipe>>,
-        candidates: C,
-    ) -> FxHashSet<StagedPath<F>>
-    where
-        C: IntoIterator<Item = F::Set>,
-    {
-        let mut result = FxHashSet::default();
+/// State of a search, for resumption.
+pub struct SearchState<F>
+where
+    F: ArcosphereFamily,
+{
+    //  The levels, in the order they were explored.
+    levels: Vec<Level<F>>,
+}
 
-        for candidate in candidates {
-            if !forward.contains_key(&candidate) || !backward.contains_key(&candidate) {
-                continue;
-            }
+impl<F> fmt::Debug for SearchState<F>
+where
+    F: ArcosphereFamily,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let searchers: usize = self.levels.iter().map(|level| level.searchers.len()).sum();
 
-            let mut recipes = Vec::new();
+        f.debug_struct("SearchState")
+            .field("levels", &self.levels.len())
+            .field("searchers", &searchers)
+            .finish()
+    }
+}
 
-            Self::stitch_forward(
-                self.source * self.count + self.catalysts,
-                forward,
-                candidate,
-                &mut recipes,
-            );
-            Self::stitch_backward(
-                self.target * self.count + self.catalysts,
-                backward,
-                candidate,
-                &mut recipes,
-            );
+/// Lazy iterator over the solutions of a search.
+///
+/// The catalysts space is explored one number of catalysts at a time, and all the paths found for a given number of
+/// catalysts are yielded before exploring the next.
+pub struct Solutions<'a, F, E>
+where
+    F: ArcosphereFamily,
+{
+    solver: &'a Solver<F, E>,
+    source: F::Set,
+    target: F::Set,
+    //  The recipes allowed.
+    recipes: Vec<F::Recipe>,
+    //  The next number of catalysts to explore, and the maximum number of catalysts to explore.
+    catalysts: Range<usize>,
+    //  The catalysts available, if restricted.
+    available: Option<F::Set>,
+    //  The paths found, not yet yielded.
+    pending: vec::IntoIter<StagedPath<F>>,
+    //  The midpoint of each path found by a search.
+    midpoints: HashMap<StagedPath<F>, F::Set>,
+    //  Whether any path was found.
+    found: bool,
+    //  The error which stopped the search, if any.
+    error: Option<ResolutionError>,
+    //  The last non-fatal error, reported if no path is ever found.
+    last_error: Option<ResolutionError>,
+    //  The suspended searches, if resumption is desired.
+    suspended: Option<Vec<Level<F>>>,
+    //  Whether to stop at the first path found.
+    first: bool,
+    //  The instant at which the search times out, if any.
+    deadline: Option<Instant>,
+    stats: SolveStats,
+}
 
-            let path = Path {
-                source: self.source,
-                target: self.target,
-                count: self.count,
-                catalysts: self.catalysts,
-                recipes,
-            };
+impl<'a, F, E> Solutions<'a, F, E>
+where
+    F: ArcosphereFamily<Arcosphere: Send, Set: Send, Recipe: Send + Sync> + Send,
+    E: Executor,
+{
+    /// Returns the error which prevented finding any further path, if any.
+    ///
+    /// Once the iterator is exhausted, an error is returned either if the search was interrupted -- definitive error,
+    /// cancellation, or timeout -- or if no path was found at all.
+    pub fn error(&self) -> Option<ResolutionError> {
+        if self.error.is_some() {
+            return self.error;
+        }
 
-            result.insert(StagedPath::parallelize(path));
+        if self.found || !self.catalysts.is_empty() {
+            return None;
         }
 
-        debug_assert_ne!(0, result.len());
+        Some(self.exhausted_error())
+    }
 
-        result
+    /// Returns the statistics of the search so far.
+    pub fn stats(&self) -> SolveStats {
+        self.stats
     }
 
-    fn stitch_forward(
-        _source: F::Set,
-        forward: &FxHashMap<F::Set, F::Recipe>,
-        candidate: F::Set,
-        recipes: &mut Vec<F::Recipe>,
-    ) {
-        let mut step = candidate;
+    /// Returns the midpoint of a path found so far, if any.
+    ///
+    /// The midpoint is the set of arcospheres, catalysts included, where the forward search from the source met the
+    /// backward search from the target. Paths found without searching, such as the trivial paths, have no midpoint.
+    pub fn midpoint(&self, path: &StagedPath<F>) -> Option<F::Set> {
+        self.midpoints.get(path).copied()
+    }
 
-        while let Some(recipe) = forward.get(&step) {
-            step = step - recipe.output() + recipe.input();
+    /// Collects the remaining paths, retaining only the best ones.
+    ///
+    /// The best paths are the shortest, in number of stages then recipes, unless a cost function is used, in which case
+    /// they are the cheapest. With `Objective::LowestScarcity`, only the paths with the least scarce catalysts are
+    /// considered.
+    pub fn best(&mut self) -> Result<Vec<StagedPath<F>>, ResolutionError> {
+        let results = self.all()?;
 
-            recipes.push(*recipe);
-        }
+        Ok(self.solver.retain_best(results))
+    }
 
-        debug_assert_eq!(_source, step);
+    /// Collects the remaining paths, retaining only the best ones, alongside the error which prevented finding any
+    /// further path, if any.
+    ///
+    /// Unlike `best`, the paths found before the search was interrupted, for example by cancellation or timeout, are
+    /// returned.
+    pub fn best_so_far(&mut self) -> (Vec<StagedPath<F>>, Option<ResolutionError>) {
+        let results = self.by_ref().collect();
 
-        recipes.reverse();
+        (self.solver.retain_best(results), self.error())
     }
 
-    fn stitch_backward(
-        _target: F::Set,
-        backward: &FxHashMap<F::Set, Reverse<F::Recipe>>,
-        candidate: F::Set,
-        recipes: &mut Vec<F::Recipe>,
-    ) {
-        let mut step = candidate;
+    /// Collects the remaining paths, sorted from best to worst.
+    ///
+    /// See `best` for the definition of best.
+    pub fn all(&mut self) -> Result<Vec<StagedPath<F>>, ResolutionError> {
+        let mut results: Vec<_> = self.by_ref().collect();
 
-        while let Some(Reverse(recipe)) = backward.get(&step) {
-            step = step - recipe.input() + recipe.output();
+        if let Some(error) = self.error() {
+            return Err(error);
+        }
 
-            recipes.push(*recipe);
+        if results.is_empty() {
+            //  Didn't find anything, it may be necessary to raise the number of catalysts or the number of recipes in a
+            //  path.
+            return Err(self.exhausted_error());
         }
 
-        debug_assert_eq!(_target, step);
+        self.solver.sort_by_rank(&mut results);
+
+        Ok(results)
     }
 }
 
-mod searcher {
-    use core::marker::PhantomData;
+impl<F, E> Iterator for Solutions<'_, F, E>
+where
+    F: ArcosphereFamily<Arcosphere: Send, Set: Send, Recipe: Send + Sync> + Send,
+    E: Executor,
+{
+    type Item = StagedPath<F>;
 
-    use crate::model::{ArcosphereRecipe, ArcosphereSet};
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_until(usize::MAX)
+    }
+}
 
-    use super::*;
+//
+//  Implementation
+//
 
-    pub(super) trait DirectionSearcher {
-        //  The set of arcospheres to use.
-        type Set: ArcosphereSet;
+const ONE: NonZeroU8 = NonZeroU8::new(1).unwrap();
 
-        //  The recipe to use.
-        type Recipe: Copy + fmt::Debug;
+impl<'a, F, E> Solutions<'a, F, E>
+where
+    F: ArcosphereFamily,
+{
+    fn new(solver: &'a Solver<F, E>, source: F::Set, target: F::Set) -> Self {
+        let recipes = solver.recipes().collect();
+        let catalysts = solver.configuration.catalysts();
+        let deadline = solver.configuration.deadline();
 
-        #[allow(dead_code)]
-        fn direction(&self) -> &'static str;
+        Self {
+            solver,
+            source,
+            target,
+            recipes,
+            catalysts,
+            available: None,
+            pending: Vec::new().into_iter(),
+            midpoints: HashMap::default(),
+            found: false,
+            error: None,
+            last_error: None,
+            suspended: None,
+            first: false,
+            deadline,
+            stats: SolveStats::default(),
+        }
+    }
 
-        fn all_recipes(&self) -> impl Iterator<Item = Self::Recipe>;
+    fn failed(solver: &'a Solver<F, E>, error: ResolutionError) -> Self {
+        let set = F::Set::default();
 
-        fn extract_recipe(&self, recipe: Self::Recipe) -> (Self::Set, Self::Set);
+        let mut this = Self::new(solver, set, set);
+        this.catalysts = 0..0;
+        this.error = Some(error);
 
-        //  Never overriden.
-        fn fold(
-            &self,
-            start: Self::Set,
-            known: &FxHashMap<Self::Set, Self::Recipe>,
-            inputs: &FxHashSet<Self::Set>,
-            outputs: &mut FxHashMap<Self::Set, Self::Recipe>,
-        ) {
-            outputs.clear();
+        this
+    }
 
-            for &input in inputs {
-                for recipe in self.all_recipes() {
-                    let (from, to) = self.extract_recipe(recipe);
+    fn trivial(solver: &'a Solver<F, E>, path: StagedPath<F>) -> Self {
+        let mut this = Self::new(solver, path.path.source, path.path.target);
+        this.catalysts = 0..0;
+        this.pending = vec![path].into_iter();
+        this.found = true;
 
-                    if !from.is_subset_of(&input) {
-                        continue;
-                    }
+        this
+    }
 
-                    let output = input - from + to;
+    //  Stops the search at the first path found.
+    fn with_first(mut self) -> Self {
+        self.first = true;
 
-                    if output == start
-                        || inputs.contains(&output)
-                        || outputs.contains_key(&output)
-                        || known.contains_key(&output)
-                    {
-                        continue;
-                    }
+        self
+    }
 
-                    outputs.insert(output, recipe);
-                }
-            }
-        }
+    //  Keeps the searches which may be resumed, on failure.
+    fn with_suspended(mut self) -> Self {
+        self.suspended = Some(Vec::new());
+
+        self
+    }
+
+    //  Draws the catalysts from `available` only, hence explores at most as many catalysts as available.
+    fn with_available_catalysts(mut self, available: F::Set) -> Self {
+        self.catalysts.end = cmp::min(self.catalysts.end, available.len() + 1);
+        self.available = Some(available);
+
+        self
     }
 
-    pub(super) struct ForwardSearcher<F> {
-        _marker: PhantomData<fn(F) -> F>,
+    //  Times the search out at `deadline`, rather than after the time budget from now, to share the budget between
+    //  multiple searches.
+    fn with_deadline(mut self, deadline: Option<Instant>) -> Self {
+        self.deadline = deadline;
+
+        self
     }
 
-    pub(super) struct BackwardSearcher<F> {
-        _marker: PhantomData<fn(F) -> F>,
+    //  Returns the error to report when no path was found, after exhausting the search space.
+    fn exhausted_error(&self) -> ResolutionError {
+        self.last_error.unwrap_or(self.solver.configuration.outside_catalysts())
     }
+}
+
+impl<F, E> Solutions<'_, F, E>
+where
+    F: ArcosphereFamily<Arcosphere: Send, Set: Send, Recipe: Send + Sync> + Send,
+    E: Executor,
+{
+    //  Returns the next path, exploring up to `maximum` catalysts, so that greater numbers of catalysts are left
+    //  unexplored until a later call with a greater `maximum`.
+    fn next_until(&mut self, maximum: usize) -> Option<StagedPath<F>> {
+        loop {
+            if let Some(path) = self.pending.next() {
+                return Some(path);
+            }
+
+            if self.error.is_some() || self.catalysts.start > maximum {
+                return None;
+            }
+
+            let i = self.catalysts.next()?;
 
-    impl<F> ForwardSearcher<F> {
-        pub(super) fn new(_family: F) -> Self {
-            Self { _marker: PhantomData }
+            self.explore(i);
         }
     }
 
-    impl<F> BackwardSearcher<F> {
-        pub(super) fn new(_family: F) -> Self {
-            Self { _marker: PhantomData }
+    //  Explores the count space for `i` catalysts, queuing the paths found.
+    //
+    //  If batching, the count space for all remaining numbers of catalysts is explored at once instead.
+    fn explore(&mut self, i: usize) {
+        if let Err(e) = self.solver.check_interruption(self.deadline) {
+            self.error = Some(e);
+            return;
+        }
+
+        let limit = self.solver.configuration.maximum_catalyst_permutations;
+
+        let Some(candidates) = self.generate_catalysts(i) else {
+            //  Greater numbers of catalysts would only generate more permutations, hence the search stops here, and the
+            //  limit is reported if no path is found.
+            self.catalysts.end = i;
+            self.last_error = Some(ResolutionError::OutsidePermutations {
+                maximum_catalyst_permutations: limit,
+            });
+            return;
+        };
+
+        if self.solver.configuration.batch_catalysts && !self.first {
+            self.explore_batch(i, candidates);
+            return;
         }
+
+        let result = self.solver.explore_count_space(
+            i,
+            &candidates,
+            self.source,
+            self.target,
+            &self.recipes,
+            &mut self.stats,
+            self.suspended.as_mut(),
+            self.first,
+            self.deadline,
+        );
+
+        self.queue(i, result);
     }
 
-    impl<F> DirectionSearcher for ForwardSearcher<F>
-    where
-        F: ArcosphereFamily,
-    {
-        type Set = F::Set;
+    //  Explores the count space for `i` catalysts, and all remaining numbers of catalysts, at once.
+    fn explore_batch(&mut self, i: usize, candidates: Vec<F::Set>) {
+        let limit = self.solver.configuration.maximum_catalyst_permutations;
 
-        type Recipe = F::Recipe;
+        let mut candidates = vec![(i, candidates)];
+        let mut exceeded = None;
 
-        fn direction(&self) -> &'static str {
-            "forward"
+        for j in self.catalysts.clone() {
+            let Some(sets) = self.generate_catalysts(j) else {
+                exceeded = Some(j);
+                break;
+            };
+
+            candidates.push((j, sets));
         }
 
-        fn all_recipes(&self) -> impl Iterator<Item = Self::Recipe> {
-            (0..F::Recipe::DIMENSION).map(F::Recipe::from_index)
+        let results = self.solver.explore_catalysts_space(
+            &candidates,
+            self.source,
+            self.target,
+            &self.recipes,
+            &mut self.stats,
+            self.suspended.as_mut(),
+            self.deadline,
+        );
+
+        for (j, result) in results {
+            if self.error.is_some() || j >= self.catalysts.end {
+                break;
+            }
+
+            self.queue(j, result);
         }
 
-        fn extract_recipe(&self, recipe: Self::Recipe) -> (Self::Set, Self::Set) {
-            (recipe.input(), recipe.output())
+        //  As in `explore`, only if the number of catalysts would have been reached.
+        if let Some(j) = exceeded.filter(|&j| j < self.catalysts.end) {
+            self.catalysts.end = j;
+            self.last_error = Some(ResolutionError::OutsidePermutations {
+                maximum_catalyst_permutations: limit,
+            });
         }
+
+        self.catalysts.start = self.catalysts.end;
     }
 
-    impl<F> DirectionSearcher for BackwardSearcher<F>
-    where
-        F: ArcosphereFamily,
-    {
-        type Set = F::Set;
+    //  Generates the sets of `i` catalysts to explore, drawn from the available catalysts if restricted, or fails if there
+    //  are more than the maximum number of permutations.
+    fn generate_catalysts(&self, i: usize) -> Option<Vec<F::Set>> {
+        let limit = self.solver.configuration.maximum_catalyst_permutations;
 
-        type Recipe = Reverse<F::Recipe>;
+        match self.available {
+            Some(available) => Searcher::<F>::generate_available_catalysts(available, i, limit),
+            None => Searcher::<F>::generate_catalysts(i, limit),
+        }
+    }
 
-        fn direction(&self) -> &'static str {
-            "backward"
+    //  Queues the paths found for `i` catalysts, after those already pending, or records the error.
+    fn queue(&mut self, i: usize, result: Result<HashMap<StagedPath<F>, F::Set>, ResolutionError>) {
+        match result {
+            Ok(paths) => {
+                //  The order of paths is total, hence the order is reproducible regardless of the executor.
+                let mut sorted: Vec<_> = paths.keys().cloned().collect();
+                sorted.sort_unstable();
+
+                let mut pending: Vec<_> = mem::take(&mut self.pending).collect();
+                pending.extend(sorted);
+
+                self.pending = pending.into_iter();
+                self.midpoints.extend(paths);
+            }
+            Err(e) if e.is_definitive() => self.error = Some(e),
+            Err(e) if e.is_interrupted() => self.error = Some(e),
+            //  Finding paths with too many stages is the most actionable error, hence it sticks.
+            Err(e @ ResolutionError::OutsideStages { .. }) => self.last_error = Some(e),
+            //  Hitting the bound on the number of recipes is the more actionable error, hence it sticks.
+            Err(e @ ResolutionError::OutsideRecipes { .. })
+                if !matches!(self.last_error, Some(ResolutionError::OutsideStages { .. })) =>
+            {
+                self.last_error = Some(e)
+            }
+            Err(e @ ResolutionError::OutsideCount { .. }) if self.last_error.is_none() => self.last_error = Some(e),
+            _ => (),
         }
 
-        fn all_recipes(&self) -> impl Iterator<Item = Self::Recipe> {
-            (0..F::Recipe::DIMENSION).map(|i| Reverse(F::Recipe::from_index(i)))
+        if self.pending.len() > 0 && !self.found {
+            self.found = true;
+
+            let extra_catalysts = self.solver.configuration.extra_catalysts as usize;
+
+            self.catalysts.end = cmp::min(self.catalysts.end, i + extra_catalysts + 1);
         }
+    }
+}
 
-        fn extract_recipe(&self, recipe: Self::Recipe) -> (Self::Set, Self::Set) {
-            let Reverse(recipe) = recipe;
+impl SolverConfiguration {
+    //  Returns the deadline of a search starting now, if any.
+    //
+    //  A budget so large the deadline overflows is as good as no budget at all.
+    fn deadline(&self) -> Option<Instant> {
+        self.time_budget.and_then(|budget| Instant::now().checked_add(budget))
+    }
 
-            (recipe.output(), recipe.input())
+    fn outside_catalysts(&self) -> ResolutionError {
+        ResolutionError::OutsideCatalysts {
+            maximum_catalysts: self.maximum_catalysts,
         }
     }
-} // mod searcher
 
-#[cfg(test)]
-mod tests {
-    use crate::{
-        executor::DefaultExecutor,
-        model::Path,
-        space_exploration::{SeArcosphereFamily, SeArcosphereRecipe, SeArcosphereSet, SeStagedPath},
-    };
+    fn outside_count(&self) -> ResolutionError {
+        ResolutionError::OutsideCount {
+            maximum_repetitions: self.maximum_repetitions,
+        }
+    }
 
-    use super::*;
+    fn outside_recipes(&self) -> ResolutionError {
+        ResolutionError::OutsideRecipes {
+            maximum_recipes: self.maximum_recipes,
+        }
+    }
 
-    #[test]
-    fn size() {
-        assert_eq!(26, core::mem::size_of::<Searcher<SeArcosphereFamily>>());
+    fn outside_stages(&self) -> ResolutionError {
+        ResolutionError::OutsideStages {
+            maximum_stages: self.maximum_stages,
+        }
     }
 
-    #[test]
-    fn solve_zero() {
-        let set = "EL".parse().unwrap();
+    fn catalysts(&self) -> Range<usize> {
+        let start = self.minimum_catalysts as usize;
+        let end = self.maximum_catalysts as usize + 1;
 
-        let expected = vec![StagedPath {
-            path: Path {
-                source: set,
-                target: set,
-                count: ONE,
-                catalysts: SeArcosphereSet::new(),
-                recipes: Vec::new(),
-            },
-            stages: Vec::new(),
-        }];
+        start..end
+    }
 
-        let paths = solve(set, set);
+    fn repetitions(&self) -> RangeInclusive<u8> {
+        1..=self.maximum_repetitions
+    }
 
-        assert_eq!(expected, paths);
+    //  Returns the successive bounds on the number of recipes.
+    fn recipes(&self) -> impl Iterator<Item = u8> + use<> {
+        let maximum = self.maximum_recipes;
+
+        let step = if self.deepening_step == 0 {
+            maximum
+        } else {
+            self.deepening_step
+        };
+
+        iter::successors(Some(cmp::min(step, maximum)), move |&bound| {
+            (bound < maximum).then(|| cmp::min(bound.saturating_add(step), maximum))
+        })
     }
+}
 
-    #[test]
-    fn solve_one() {
-        let source = "EO".parse().unwrap();
-        let target = "LG".parse().unwrap();
+impl<F, E> Solver<F, E>
+where
+    F: ArcosphereFamily<Arcosphere: Send, Set: Send, Recipe: Send + Sync> + Send,
+    E: Executor,
+{
+    //  Retains only the best paths, sorted.
+    fn retain_best(&self, mut results: Vec<StagedPath<F>>) -> Vec<StagedPath<F>> {
+        self.sort_by_rank(&mut results);
 
-        let expected = vec![StagedPath {
-            path: Path {
+        let Some(best) = results.first().map(|p| self.rank(p)) else {
+            return results;
+        };
+
+        results.retain(|p| self.rank(p) == best);
+
+        results
+    }
+
+    //  Sorts the paths by rank, removing duplicates.
+    fn sort_by_rank(&self, results: &mut Vec<StagedPath<F>>) {
+        let diversity = |path: &StagedPath<F>| {
+            if !self.configuration.prefer_fewer_catalyst_types {
+                return 0;
+            }
+
+            (0..F::Arcosphere::DIMENSION)
+                .filter(|&i| path.path.catalysts.contains(F::Arcosphere::from_index(i)))
+                .count()
+        };
+
+        //  Stable output is nice, and definitely not the most costly part anyway...
+        //
+        //  The order of paths is total, hence the order is reproducible regardless of the order in which they were found.
+        results.sort_unstable_by(|a, b| {
+            self.rank(a)
+                .cmp(&self.rank(b))
+                .then_with(|| diversity(a).cmp(&diversity(b)))
+                .then_with(|| a.cmp(b))
+        });
+
+        results.dedup();
+    }
+
+    //  Returns the rank of a path, the lower the better.
+    //
+    //  Without a cost function, the shortest paths -- in number of stages, then recipes -- are best, otherwise the
+    //  cheapest paths are. With `Objective::LowestScarcity`, the least scarce catalysts come first regardless.
+    fn rank(&self, path: &StagedPath<F>) -> (u64, u64, usize, usize) {
+        let scarcity = match self.configuration.objective {
+            Objective::FewestCatalysts => 0,
+            Objective::LowestScarcity => {
+                let scarcity = self.scarcity.unwrap_or(|_| 1);
+
+                path.path.catalysts.into_iter().map(|a| scarcity(a) as u64).sum()
+            }
+        };
+
+        match self.cost {
+            Some(cost) => (scarcity, path.path.recipes.iter().map(|r| cost(r) as u64).sum(), 0, 0),
+            None => (scarcity, 0, path.stage_count(), path.recipe_count()),
+        }
+    }
+
+    //  Looks for all possible recipe paths from `source` to `target` using exactly `catalysts` as catalysts, timing out
+    //  at `deadline`, if any.
+    //
+    //  See `solve_with_catalysts`.
+    fn solve_with_catalysts_until(
+        &self,
+        source: F::Set,
+        target: F::Set,
+        catalysts: F::Set,
+        deadline: Option<Instant>,
+    ) -> Result<Vec<StagedPath<F>>, ResolutionError> {
+        if source.len() != target.len() {
+            return Err(ResolutionError::PreservationError);
+        }
+
+        if source == target {
+            let path = Path {
                 source,
                 target,
                 count: ONE,
-                catalysts: SeArcosphereSet::new(),
-                recipes: vec![SeArcosphereRecipe::EO],
-            },
-            stages: Vec::new(),
-        }];
+                catalysts,
+                recipes: Vec::new(),
+            };
+
+            return Ok(vec![StagedPath::parallelize(path)]);
+        }
+
+        if self.is_polarity_restricted(source, target) {
+            return Err(self.configuration.outside_recipes());
+        }
+
+        self.check_interruption(deadline)?;
+
+        let recipes: Vec<_> = self.recipes().collect();
+        let mut stats = SolveStats::default();
+
+        let result = self.explore_count_space(
+            catalysts.len(),
+            &[catalysts],
+            source,
+            target,
+            &recipes,
+            &mut stats,
+            None,
+            false,
+            deadline,
+        );
+
+        result.map(|paths| self.retain_best(paths.into_keys().collect()))
+    }
+
+    //  Explores the count space for `catalysts` catalysts, trying each set of catalysts in `candidates`.
+    //
+    //  The counts are explored one at a time, up to the first with any path. If `first` is true, the exploration stops
+    //  as soon as a path is found, see `explore_levels`.
+    #[allow(clippy::too_many_arguments)]
+    fn explore_count_space(
+        &self,
+        catalysts: usize,
+        candidates: &[F::Set],
+        source: F::Set,
+        target: F::Set,
+        recipes: &[F::Recipe],
+        stats: &mut SolveStats,
+        suspended: Option<&mut Vec<Level<F>>>,
+        first: bool,
+        deadline: Option<Instant>,
+    ) -> Result<HashMap<StagedPath<F>, F::Set>, ResolutionError> {
+        let mut levels = Vec::new();
+
+        for count in self.counts(source, target, candidates, recipes) {
+            let mut level = self.generate_level(catalysts, count, candidates, source, target, stats);
+
+            self.explore_levels(slice::from_mut(&mut level), recipes, stats, first, deadline);
+
+            let done = !level.paths.is_empty() || level.error.is_some();
+
+            levels.push(level);
+
+            if done {
+                break;
+            }
+        }
+
+        self.fold_levels(levels, suspended)
+    }
+
+    //  Explores the count space for each number of catalysts of `candidates` at once, trying each set of catalysts.
+    //
+    //  Returns, in order, the result `explore_count_space` would return for each number of catalysts, up to the first
+    //  error or `extra_catalysts` past the first number of catalysts with any path.
+    #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+    fn explore_catalysts_space(
+        &self,
+        candidates: &[(usize, Vec<F::Set>)],
+        source: F::Set,
+        target: F::Set,
+        recipes: &[F::Recipe],
+        stats: &mut SolveStats,
+        mut suspended: Option<&mut Vec<Level<F>>>,
+        deadline: Option<Instant>,
+    ) -> Vec<(usize, Result<HashMap<StagedPath<F>, F::Set>, ResolutionError>)> {
+        let extra_catalysts = self.configuration.extra_catalysts as usize;
+
+        //  The levels, in the order `explore_count_space` explores them.
+        let mut levels: Vec<_> = candidates
+            .iter()
+            .flat_map(|(catalysts, candidates)| {
+                self.counts(source, target, candidates, recipes)
+                    .map(move |count| (*catalysts, count, candidates))
+            })
+            .map(|(catalysts, count, candidates)| {
+                self.generate_level(catalysts, count, candidates, source, target, stats)
+            })
+            .collect();
+
+        self.explore_levels(&mut levels, recipes, stats, false, deadline);
+
+        //  Folds the levels of each number of catalysts, as `explore_count_space` would.
+        let mut results = Vec::with_capacity(candidates.len());
+        let mut levels = levels.into_iter().peekable();
+        let mut minimum = None;
+
+        for &(catalysts, _) in candidates {
+            if minimum.is_some_and(|minimum| catalysts > minimum + extra_catalysts) {
+                break;
+            }
+
+            let same = iter::from_fn(|| levels.next_if(|level| level.level.catalysts == catalysts));
+
+            let result = self.fold_levels(same, suspended.as_deref_mut());
+
+            if result.is_ok() {
+                minimum.get_or_insert(catalysts);
+            }
+
+            let stop = matches!(result, Err(e) if e.is_definitive() || e.is_interrupted());
+
+            results.push((catalysts, result));
+
+            if stop {
+                break;
+            }
+        }
+
+        results
+    }
+
+    //  Returns the counts to explore with `candidates`, in order.
+    //
+    //  The counts for which no combination of recipes can achieve the polarity change are skipped, and the exploration
+    //  stops at the first count which overflows, as higher counts would only overflow further.
+    fn counts<'a>(
+        &self,
+        source: F::Set,
+        target: F::Set,
+        candidates: &'a [F::Set],
+        recipes: &[F::Recipe],
+    ) -> impl Iterator<Item = NonZeroU8> + use<'a, F, E> {
+        //  Catalysts are returned as is, hence do not affect the polarity.
+        let polarity = target.net_polarity() - source.net_polarity();
+        let polarity_step = Self::polarity_step(recipes);
+
+        self.configuration
+            .repetitions()
+            .filter_map(NonZeroU8::new)
+            .take_while(move |&count| !Self::overflows(source, target, count, candidates))
+            .filter(move |count| Self::is_polarity_reachable(polarity * i32::from(count.get()), polarity_step))
+    }
+
+    //  Generates the level of `catalysts` catalysts & `count`, with a searcher for each set of catalysts in `candidates`.
+    fn generate_level(
+        &self,
+        catalysts: usize,
+        count: NonZeroU8,
+        candidates: &[F::Set],
+        source: F::Set,
+        target: F::Set,
+        stats: &mut SolveStats,
+    ) -> BatchLevel<F> {
+        let configuration = self.configuration.into();
+
+        let searchers: Vec<_> =
+            Searcher::generate_searchers(self.family, source, target, count, candidates, configuration)
+                .into_iter()
+                .map(Suspended::new)
+                .collect();
+
+        stats.counts_tried += 1;
+        stats.catalysts_tried += searchers.len() as u64;
+
+        BatchLevel::new(Level { catalysts, searchers })
+    }
+
+    //  Explores levels -- each a given number of catalysts & count -- at once, for each bound on the number of recipes.
+    //
+    //  On return, the searchers of each level are those for which the bound on the number of recipes was hit, or which
+    //  were interrupted, and may thus be resumed. The levels whose paths would be discarded anyway are abandoned: those
+    //  with more catalysts than the first number of catalysts with any path allows, and those with a greater count than
+    //  another level with any path for the same number of catalysts.
+    //
+    //  If `first` is true, only the paths of the first searcher -- in order -- which found any are kept, regardless of
+    //  the executor. The searchers following it which were not yet started are skipped altogether, and are not
+    //  resumable.
+    fn explore_levels(
+        &self,
+        levels: &mut [BatchLevel<F>],
+        recipes: &[F::Recipe],
+        stats: &mut SolveStats,
+        first: bool,
+        deadline: Option<Instant>,
+    ) {
+        let extra_catalysts = self.configuration.extra_catalysts as usize;
+        let cancellation = self.cancellation.as_ref();
+        //  The index of the first task which found a path, if any.
+        let found = AtomicUsize::new(usize::MAX);
+
+        for maximum_recipes in self.configuration.recipes() {
+            //  Only the levels which found nothing, yet, are explored further.
+            let is_pending = |batch: &BatchLevel<F>| batch.paths.is_empty() && batch.error.is_none();
+
+            if let Err(error) = self.check_interruption(deadline) {
+                levels
+                    .iter_mut()
+                    .filter(|batch| is_pending(batch))
+                    .for_each(|batch| batch.error = Some(error));
+
+                break;
+            }
+
+            let tasks: Vec<_> = levels
+                .iter_mut()
+                .enumerate()
+                .filter(|(_, batch)| is_pending(batch))
+                .flat_map(|(index, batch)| batch.level.searchers.drain(..).map(move |suspended| (index, suspended)))
+                .enumerate()
+                .map(|(task, (index, mut suspended))| {
+                    suspended.searcher.configuration.maximum_recipes = maximum_recipes;
+
+                    let found = &found;
+
+                    move || {
+                        let mut stats = SolveStats::default();
+
+                        if first && found.load(Ordering::Relaxed) < task {
+                            return (task, index, suspended, Ok(HashMap::default()), stats);
+                        }
+
+                        let Suspended { searcher, frontier } = &mut suspended;
+
+                        let result = searcher.solve::<E>(frontier, recipes, cancellation, deadline, &mut stats);
+
+                        if result.as_ref().is_ok_and(|paths| !paths.is_empty()) {
+                            found.fetch_min(task, Ordering::Relaxed);
+                        }
+
+                        (task, index, suspended, result, stats)
+                    }
+                })
+                .collect();
+
+            if tasks.is_empty() {
+                break;
+            }
+
+            let mut interrupted = false;
+            let mut earliest = None;
+
+            for (task, index, suspended, result, searcher_stats) in self.executor.execute(tasks) {
+                stats.merge(&searcher_stats);
+
+                let batch = &mut levels[index];
+
+                match result {
+                    Ok(paths) if first => {
+                        if !paths.is_empty() && earliest.as_ref().is_none_or(|&(earliest, _, _)| task < earliest) {
+                            earliest = Some((task, index, paths));
+                        }
+                    }
+                    Ok(paths) => batch.paths.extend(paths),
+                    Err(e) if e.is_definitive() => batch.error = Some(e),
+                    Err(ResolutionError::OutsideStages { .. }) => batch.outside_stages = true,
+                    //  Only the searchers interrupted, or hitting the bound, may benefit from deepening, or resumption.
+                    Err(e) if e.is_interrupted() => {
+                        interrupted = true;
+                        batch.error = batch.error.or(Some(e));
+                        batch.level.searchers.push(suspended);
+                    }
+                    Err(ResolutionError::OutsideRecipes { .. }) => batch.level.searchers.push(suspended),
+                    _ => (),
+                }
+            }
+
+            if let Some((_, index, paths)) = earliest {
+                levels[index].paths.extend(paths);
+            }
+
+            if interrupted {
+                break;
+            }
+
+            let minimum = levels
+                .iter()
+                .filter(|batch| !batch.paths.is_empty())
+                .map(|batch| batch.level.catalysts)
+                .min();
+
+            for i in 0..levels.len() {
+                let catalysts = levels[i].level.catalysts;
+
+                let abandoned = minimum.is_some_and(|minimum| catalysts > minimum + extra_catalysts)
+                    || levels[..i]
+                        .iter()
+                        .any(|batch| batch.level.catalysts == catalysts && !batch.paths.is_empty());
+
+                if abandoned {
+                    levels[i].level.searchers.clear();
+                }
+            }
+        }
+    }
+
+    //  Folds the explored levels of a given number of catalysts, in order, into the paths or error of the first level
+    //  with any, or else into the error to report, suspending the searchers which may be resumed, if `suspended`.
+    //
+    //  If no path is found, but some searcher found paths with too many stages, `OutsideStages` is reported, otherwise
+    //  `OutsideRecipes` if some searcher hit the bound on the number of recipes, and `OutsideCount` otherwise.
+    fn fold_levels<I>(
+        &self,
+        levels: I,
+        mut suspended: Option<&mut Vec<Level<F>>>,
+    ) -> Result<HashMap<StagedPath<F>, F::Set>, ResolutionError>
+    where
+        I: IntoIterator<Item = BatchLevel<F>>,
+    {
+        let mut last_error = None;
+        let mut stages_error = None;
+        let mut result = None;
+
+        for batch in levels {
+            if result.is_some() {
+                continue;
+            }
+
+            let BatchLevel {
+                level,
+                paths,
+                error,
+                outside_stages,
+            } = batch;
+
+            if !level.searchers.is_empty() {
+                last_error = Some(self.configuration.outside_recipes());
+
+                if let Some(suspended) = suspended.as_deref_mut() {
+                    suspended.push(level);
+                }
+            }
+
+            if let Some(error) = error {
+                result = Some(Err(error));
+            } else if !paths.is_empty() {
+                result = Some(Ok(paths));
+            } else if outside_stages {
+                stages_error = Some(self.configuration.outside_stages());
+            }
+        }
+
+        //  Didn't find anything, it may be necessary to raise the number of catalysts, the number of recipes, or the
+        //  number of stages in a path.
+        result.unwrap_or_else(|| {
+            Err(stages_error
+                .or(last_error)
+                .unwrap_or(self.configuration.outside_count()))
+        })
+    }
+
+    //  Returns whether the searchers of `count` would overflow, that is whether `source * count + catalysts` or
+    //  `target * count + catalysts` overflows for any of the candidate catalysts.
+    fn overflows(source: F::Set, target: F::Set, count: NonZeroU8, candidates: &[F::Set]) -> bool {
+        let (Some(source), Some(target)) = (source.checked_mul(count.get()), target.checked_mul(count.get())) else {
+            return true;
+        };
+
+        candidates
+            .iter()
+            .any(|&catalysts| source.checked_add(catalysts).is_none() || target.checked_add(catalysts).is_none())
+    }
+
+    //  Returns the GCD of the polarity changes of the recipes.
+    //
+    //  Any sequence of recipes changes the polarity by a multiple of this step, or not at all if the step is 0.
+    fn polarity_step(recipes: &[F::Recipe]) -> i32 {
+        recipes
+            .iter()
+            .map(|recipe| recipe.output().net_polarity() - recipe.input().net_polarity())
+            .fold(0, num_integer::gcd)
+    }
+
+    //  Returns whether a change of polarity may be achieved by recipes changing the polarity by multiples of `step`.
+    fn is_polarity_reachable(polarity: i32, step: i32) -> bool {
+        if step == 0 {
+            return polarity == 0;
+        }
+
+        polarity % step == 0
+    }
+
+    //  Returns all the sets reachable from `start`, excluding `start` itself, within `maximum_recipes` recipes.
+    fn reach(family: F, start: F::Set, recipes: &[F::Recipe], maximum_recipes: u8) -> HashSet<F::Set> {
+        use searcher::DirectionSearcher;
+
+        let searcher = searcher::ForwardSearcher::new(family, recipes);
+
+        let mut known = HashMap::default();
+        let mut inputs = HashSet::from_iter([start]);
+        let mut outputs = HashMap::default();
+
+        for _ in 0..maximum_recipes {
+            if inputs.is_empty() {
+                break;
+            }
+
+            searcher.fold(start, &known, &inputs, &mut outputs);
+
+            inputs.clear();
+            inputs.extend(outputs.keys().copied());
+
+            known.extend(outputs.drain());
+        }
+
+        known.into_keys().collect()
+    }
+
+    //  Returns whether the restriction of recipes, alone, prevents the change of polarity from `source` to `target`,
+    //  that is whether no count may achieve it with the allowed recipes, whereas some count may with all recipes.
+    //
+    //  The count space is then pruned in its entirety by the restriction, hence `OutsideRecipes` is the error to report.
+    fn is_polarity_restricted(&self, source: F::Set, target: F::Set) -> bool {
+        if !self.is_restricted() {
+            return false;
+        }
+
+        let restricted: Vec<_> = self.recipes().collect();
+        let all: Vec<_> = F::Recipe::all().collect();
+
+        let (restricted, all) = (Self::polarity_step(&restricted), Self::polarity_step(&all));
+
+        let polarity = target.net_polarity() - source.net_polarity();
+        let counts = self.configuration.repetitions();
+
+        let is_reachable = |step| {
+            counts
+                .clone()
+                .any(|count| Self::is_polarity_reachable(polarity * i32::from(count), step))
+        };
+
+        !is_reachable(restricted) && is_reachable(all)
+    }
+
+    //  Returns whether some combination of the allowed recipes may achieve the change from `source` to `target`.
+    //
+    //  Catalysts are returned as is, hence do not affect the balance, which only depends on the number of times each
+    //  recipe is applied. A rational solution is necessary, though not sufficient, for an integer solution, hence this
+    //  only ever proves the infeasibility of a problem. Since rational solutions scale, the count does not matter.
+    fn is_feasible(&self, source: F::Set, target: F::Set) -> bool {
+        let columns: Vec<_> = self.recipes().map(|recipe| recipe.into_index()).collect();
+
+        let mut delta = vec![0; F::Arcosphere::DIMENSION];
+
+        for sphere in target {
+            delta[sphere.into_index()] += 1;
+        }
+
+        for sphere in source {
+            delta[sphere.into_index()] -= 1;
+        }
+
+        feasibility::is_feasible(&F::recipe_matrix(), &columns, &delta)
+    }
+
+    //  Checks whether the search was cancelled, or exceeded its `deadline`, if any.
+    fn check_interruption(&self, deadline: Option<Instant>) -> Result<(), ResolutionError> {
+        if self.cancellation.as_ref().is_some_and(Cancellation::is_cancelled) {
+            return Err(ResolutionError::Cancelled);
+        }
+
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Err(ResolutionError::Timeout);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct SearcherConfiguration {
+    maximum_recipes: u8,
+    maximum_stages: u8,
+    concurrent_directions: bool,
+}
+
+impl From<SolverConfiguration> for SearcherConfiguration {
+    fn from(value: SolverConfiguration) -> SearcherConfiguration {
+        let SolverConfiguration {
+            maximum_recipes,
+            maximum_stages,
+            concurrent_directions,
+            ..
+        } = value;
+
+        SearcherConfiguration {
+            maximum_recipes,
+            maximum_stages,
+            concurrent_directions,
+        }
+    }
+}
+
+struct Searcher<F>
+where
+    F: ArcosphereFamily,
+{
+    family: F,
+    source: F::Set,
+    target: F::Set,
+    count: NonZeroU8,
+    catalysts: F::Set,
+    configuration: SearcherConfiguration,
+}
+
+//  The state of the bidirectional search of a searcher.
+struct Frontier<F>
+where
+    F: ArcosphereFamily,
+{
+    //  The number of iterations performed so far.
+    iterations: usize,
+    forward: HashMap<F::Set, F::Recipe>,
+    backward: HashMap<F::Set, Reverse<F::Recipe>>,
+    in_forward: HashSet<F::Set>,
+    in_backward: HashSet<F::Set>,
+    out_forward: HashMap<F::Set, F::Recipe>,
+    out_backward: HashMap<F::Set, Reverse<F::Recipe>>,
+}
+
+//  The known sets, inputs, and outputs of one direction of a frontier.
+type DirectionSets<S, R> = (HashMap<S, R>, HashSet<S>, HashMap<S, R>);
+
+//  A searcher, and the state of its search.
+struct Suspended<F>
+where
+    F: ArcosphereFamily,
+{
+    searcher: Searcher<F>,
+    frontier: Frontier<F>,
+}
+
+impl<F> Suspended<F>
+where
+    F: ArcosphereFamily,
+{
+    fn new(searcher: Searcher<F>) -> Self {
+        let source = searcher.source * searcher.count + searcher.catalysts;
+        let target = searcher.target * searcher.count + searcher.catalysts;
+
+        let frontier = Frontier {
+            iterations: 0,
+            forward: HashMap::default(),
+            backward: HashMap::default(),
+            in_forward: HashSet::from_iter([source]),
+            in_backward: HashSet::from_iter([target]),
+            out_forward: HashMap::default(),
+            out_backward: HashMap::default(),
+        };
+
+        Self { searcher, frontier }
+    }
+}
+
+//  The suspended searchers of a given number of catalysts & count.
+struct Level<F>
+where
+    F: ArcosphereFamily,
+{
+    catalysts: usize,
+    searchers: Vec<Suspended<F>>,
+}
+
+//  A level being explored, possibly alongside others, with the paths found or the error encountered so far.
+struct BatchLevel<F>
+where
+    F: ArcosphereFamily,
+{
+    level: Level<F>,
+    paths: HashMap<StagedPath<F>, F::Set>,
+    error: Option<ResolutionError>,
+    //  Whether any searcher found paths with too many stages only.
+    outside_stages: bool,
+}
+
+impl<F> BatchLevel<F>
+where
+    F: ArcosphereFamily,
+{
+    fn new(level: Level<F>) -> Self {
+        Self {
+            level,
+            paths: HashMap::default(),
+            error: None,
+            outside_stages: false,
+        }
+    }
+}
+
+impl<F> Searcher<F>
+where
+    F: ArcosphereFamily,
+{
+    fn generate_searchers(
+        family: F,
+        source: F::Set,
+        target: F::Set,
+        count: NonZeroU8,
+        candidates: &[F::Set],
+        configuration: SearcherConfiguration,
+    ) -> Vec<Searcher<F>> {
+        candidates
+            .iter()
+            .map(|&catalysts| Searcher {
+                family,
+                source,
+                target,
+                count,
+                catalysts,
+                configuration,
+            })
+            .collect()
+    }
+
+    //  Generates all permutations of `number` spheres, or fails if there are more than `limit`.
+    //
+    //  The generated number of permutations is combinatorial, that is the number of multisets of `number` spheres out
+    //  of `DIMENSION` arcospheres, or `(DIMENSION + number - 1)! / (number! * (DIMENSION - 1)!)`. With 8 arcospheres:
+    //
+    //  -   0: 0.
+    //  -   1: 8, one of each.
+    //  -   2: 36, at each level 8, then 7, then 6, etc...
+    //  -   3: 120.
+    //  -   4: 330.
+    //  -   5: 792.
+    //
+    //  With 16 arcospheres, 5 catalysts already result in 15504 permutations, and 8 catalysts in 490314.
+    fn generate_catalysts(number: usize, limit: usize) -> Option<Vec<F::Set>> {
+        let mut result = Vec::new();
+
+        if number == 0 {
+            return Some(result);
+        }
+
+        Self::generate_catalysts_rec(F::Set::default(), None, number, limit, &mut result)?;
+
+        Some(result)
+    }
+
+    //  Generates all permutations of `number` spheres drawn from `available`, or fails if there are more than `limit`.
+    //
+    //  Unlike `generate_catalysts`, the empty set is generated for 0 catalysts, so that the paths requiring no catalyst
+    //  are explored too. See `generate_catalysts`.
+    fn generate_available_catalysts(available: F::Set, number: usize, limit: usize) -> Option<Vec<F::Set>> {
+        if number == 0 {
+            return Some(vec![F::Set::default()]);
+        }
+
+        let mut result = Vec::new();
+
+        Self::generate_catalysts_rec(F::Set::default(), Some(available), number, limit, &mut result)?;
+
+        Some(result)
+    }
+
+    fn generate_catalysts_rec(
+        catalysts: F::Set,
+        available: Option<F::Set>,
+        number: usize,
+        limit: usize,
+        output: &mut Vec<F::Set>,
+    ) -> Option<()> {
+        debug_assert!(number > 0);
+
+        //  Do not insert spheres with a lower index than the highest index sphere used: it only creates duplicates.
+        let minimum = catalysts
+            .into_iter()
+            .last()
+            .map(|sphere| sphere.into_index())
+            .unwrap_or_default();
+
+        let generator = (minimum..F::Arcosphere::DIMENSION)
+            .map(|i| {
+                let mut catalysts = catalysts;
+
+                catalysts.insert(F::Arcosphere::from_index(i));
+
+                catalysts
+            })
+            .filter(|catalysts| available.is_none_or(|available| available.is_superset_of(catalysts)));
+
+        if number == 1 {
+            output.extend(generator);
+
+            if output.len() > limit {
+                return None;
+            }
+
+            return Some(());
+        }
+
+        for catalysts in generator {
+            Self::generate_catalysts_rec(catalysts, available, number - 1, limit, output)?;
+        }
+
+        Some(())
+    }
+}
+
+impl<F> Searcher<F>
+where
+    F: ArcosphereFamily,
+{
+    //  Each iteration expands both directions, concurrently as per `E::join` if so configured, then checks for a
+    //  connection forward first, then backward, so that the paths found are those of a serial search.
+    //
+    //  Returns no path if the search is exhausted, ie no path exists with these catalysts & count.
+    fn solve<E>(
+        &self,
+        frontier: &mut Frontier<F>,
+        recipes: &[F::Recipe],
+        cancellation: Option<&Cancellation>,
+        deadline: Option<Instant>,
+        stats: &mut SolveStats,
+    ) -> Result<HashMap<StagedPath<F>, F::Set>, ResolutionError>
+    where
+        F: ArcosphereFamily<Set: Send, Recipe: Send + Sync>,
+        E: Executor,
+    {
+        let maximum_iterations = (self.configuration.maximum_recipes as usize + 1) / 2;
+
+        let source = self.source * self.count + self.catalysts;
+        let target = self.target * self.count + self.catalysts;
+
+        let Frontier {
+            iterations,
+            forward,
+            backward,
+            in_forward,
+            in_backward,
+            out_forward,
+            out_backward,
+        } = frontier;
+
+        while *iterations < maximum_iterations {
+            if cancellation.is_some_and(Cancellation::is_cancelled) {
+                return Err(ResolutionError::Cancelled);
+            }
+
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Err(ResolutionError::Timeout);
+            }
+
+            if in_forward.is_empty() && in_backward.is_empty() {
+                return Ok(HashMap::default());
+            }
+
+            *iterations += 1;
+
+            stats.forward_expanded += in_forward.len() as u64;
+            stats.backward_expanded += in_backward.len() as u64;
+
+            let forward_searcher = searcher::ForwardSearcher::new(self.family, recipes);
+            let backward_searcher = searcher::BackwardSearcher::new(self.family, recipes);
+
+            //  Each direction only accesses its own sets, moved in & out of its task, hence neither observes the other
+            //  mid-expansion.
+            let expand_forward = Self::expand(forward_searcher, source, forward, in_forward, out_forward);
+            let expand_backward = Self::expand(backward_searcher, target, backward, in_backward, out_backward);
+
+            let (expanded_forward, expanded_backward) = if self.configuration.concurrent_directions {
+                E::join(expand_forward, expand_backward)
+            } else {
+                (expand_forward(), expand_backward())
+            };
+
+            (*forward, *in_forward, *out_forward) = expanded_forward;
+            (*backward, *in_backward, *out_backward) = expanded_backward;
+
+            let matched = Self::advance(forward, in_forward, out_forward, backward);
+
+            stats.peak_forward = cmp::max(stats.peak_forward, forward.len());
+
+            //  The backward expansion is left pending, to be redone from the same inputs should the search resume.
+            if matched {
+                return self.retain_stages(self.stitch(forward, backward, out_forward.keys().copied()));
+            }
+
+            let matched = Self::advance(backward, in_backward, out_backward, forward);
+
+            stats.peak_backward = cmp::max(stats.peak_backward, backward.len());
+
+            if matched {
+                return self.retain_stages(self.stitch(forward, backward, out_backward.keys().copied()));
+            }
+        }
+
+        if in_forward.is_empty() && in_backward.is_empty() {
+            return Ok(HashMap::default());
+        }
+
+        Err(ResolutionError::OutsideRecipes {
+            maximum_recipes: self.configuration.maximum_recipes,
+        })
+    }
+
+    //  Returns a task expanding the inputs of a direction, taking the sets of that direction and handing them back.
+    fn expand<S>(
+        searcher: S,
+        start: F::Set,
+        known: &mut HashMap<F::Set, S::Recipe>,
+        inputs: &mut HashSet<F::Set>,
+        outputs: &mut HashMap<F::Set, S::Recipe>,
+    ) -> impl FnOnce() -> DirectionSets<F::Set, S::Recipe> + Send + use<S, F>
+    where
+        F::Set: Send,
+        S: searcher::DirectionSearcher<Set = F::Set> + Send,
+        S::Recipe: Send,
+    {
+        let (known, inputs, mut outputs) = (mem::take(known), mem::take(inputs), mem::take(outputs));
+
+        move || {
+            searcher.fold(start, &known, &inputs, &mut outputs);
+
+            (known, inputs, outputs)
+        }
+    }
+
+    //  Moves the outputs of an expansion into the known sets, and the next inputs.
+    //
+    //  Returns true if a connection has been found.
+    fn advance<R, OR>(
+        known: &mut HashMap<F::Set, R>,
+        inputs: &mut HashSet<F::Set>,
+        outputs: &HashMap<F::Set, R>,
+        opposite_known: &HashMap<F::Set, OR>,
+    ) -> bool
+    where
+        R: Copy,
+    {
+        inputs.clear();
+        inputs.extend(outputs.keys().copied());
+
+        known.extend(outputs.iter().map(|(&key, &value)| (key, value)));
+
+        outputs.keys().any(|key| opposite_known.contains_key(key))
+    }
+
+    fn stitch<C>(
+        &self,
+        forward: &HashMap<F::Set, F::Recipe>,
+        backward: &HashMap<F::Set, Reverse<F::Recipe>>,
+        candidates: C,
+    ) -> HashMap<StagedPath<F>, F::Set>
+    where
+        C: IntoIterator<Item = F::Set>,
+    {
+        let mut result = HashMap::default();
+
+        for candidate in candidates {
+            if !forward.contains_key(&candidate) || !backward.contains_key(&candidate) {
+                continue;
+            }
+
+            let mut recipes = Vec::new();
+
+            Self::stitch_forward(
+                self.source * self.count + self.catalysts,
+                forward,
+                candidate,
+                &mut recipes,
+            );
+            Self::stitch_backward(
+                self.target * self.count + self.catalysts,
+                backward,
+                candidate,
+                &mut recipes,
+            );
+
+            let path = Path {
+                source: self.source,
+                target: self.target,
+                count: self.count,
+                catalysts: self.catalysts,
+                recipes,
+            };
+
+            //  Several midpoints may lead to the same path, the lowest is retained for reproducibility.
+            result
+                .entry(StagedPath::parallelize(path))
+                .and_modify(|midpoint: &mut F::Set| *midpoint = cmp::min(*midpoint, candidate))
+                .or_insert(candidate);
+        }
+
+        debug_assert_ne!(0, result.len());
+
+        result
+    }
+
+    //  Discards the paths with too many stages, reporting `OutsideStages` if none is left.
+    fn retain_stages(
+        &self,
+        mut paths: HashMap<StagedPath<F>, F::Set>,
+    ) -> Result<HashMap<StagedPath<F>, F::Set>, ResolutionError> {
+        let maximum_stages = self.configuration.maximum_stages;
+
+        paths.retain(|path, _| path.stage_count() <= maximum_stages as usize);
+
+        if paths.is_empty() {
+            return Err(ResolutionError::OutsideStages { maximum_stages });
+        }
+
+        Ok(paths)
+    }
+
+    fn stitch_forward(
+        _source: F::Set,
+        forward: &HashMap<F::Set, F::Recipe>,
+        candidate: F::Set,
+        recipes: &mut Vec<F::Recipe>,
+    ) {
+        let mut step = candidate;
+
+        while let Some(recipe) = forward.get(&step) {
+            step = step - recipe.output() + recipe.input();
+
+            recipes.push(*recipe);
+        }
+
+        debug_assert_eq!(_source, step);
+
+        recipes.reverse();
+    }
+
+    fn stitch_backward(
+        _target: F::Set,
+        backward: &HashMap<F::Set, Reverse<F::Recipe>>,
+        candidate: F::Set,
+        recipes: &mut Vec<F::Recipe>,
+    ) {
+        let mut step = candidate;
+
+        while let Some(Reverse(recipe)) = backward.get(&step) {
+            step = step - recipe.input() + recipe.output();
+
+            recipes.push(*recipe);
+        }
+
+        debug_assert_eq!(_target, step);
+    }
+}
+
+//  The hashing feature selected, see `HashBuilder`.
+mod hashing {
+    #[cfg(any(feature = "std-hash", not(any(feature = "ahash", feature = "fxhash"))))]
+    pub type HashBuilder = std::hash::RandomState;
+
+    #[cfg(all(feature = "ahash", not(feature = "std-hash")))]
+    pub type HashBuilder = ahash::RandomState;
+
+    #[cfg(all(feature = "fxhash", not(any(feature = "ahash", feature = "std-hash"))))]
+    pub type HashBuilder = fxhash::FxBuildHasher;
+} // mod hashing
+
+mod searcher {
+    use crate::model::{ArcosphereRecipe, ArcosphereSet};
+
+    use super::*;
+
+    pub(super) trait DirectionSearcher {
+        //  The set of arcospheres to use.
+        type Set: ArcosphereSet;
+
+        //  The recipe to use.
+        type Recipe: Copy + fmt::Debug + Ord;
+
+        #[allow(dead_code)]
+        fn direction(&self) -> &'static str;
+
+        fn all_recipes(&self) -> impl Iterator<Item = Self::Recipe>;
+
+        fn extract_recipe(&self, recipe: Self::Recipe) -> (Self::Set, Self::Set);
+
+        //  Never overriden.
+        fn fold(
+            &self,
+            start: Self::Set,
+            known: &HashMap<Self::Set, Self::Recipe>,
+            inputs: &HashSet<Self::Set>,
+            outputs: &mut HashMap<Self::Set, Self::Recipe>,
+        ) {
+            outputs.clear();
+
+            for &input in inputs {
+                for recipe in self.all_recipes() {
+                    let (from, to) = self.extract_recipe(recipe);
+
+                    if !from.is_subset_of(&input) {
+                        continue;
+                    }
+
+                    let output = input - from + to;
+
+                    if output == start || inputs.contains(&output) || known.contains_key(&output) {
+                        continue;
+                    }
+
+                    //  The lowest recipe is retained, so the outputs do not depend on the iteration order of the inputs.
+                    outputs
+                        .entry(output)
+                        .and_modify(|known: &mut Self::Recipe| *known = cmp::min(*known, recipe))
+                        .or_insert(recipe);
+                }
+            }
+        }
+    }
+
+    pub(super) struct ForwardSearcher<'a, F>
+    where
+        F: ArcosphereFamily,
+    {
+        recipes: &'a [F::Recipe],
+    }
+
+    pub(super) struct BackwardSearcher<'a, F>
+    where
+        F: ArcosphereFamily,
+    {
+        recipes: &'a [F::Recipe],
+    }
+
+    impl<'a, F> ForwardSearcher<'a, F>
+    where
+        F: ArcosphereFamily,
+    {
+        pub(super) fn new(_family: F, recipes: &'a [F::Recipe]) -> Self {
+            Self { recipes }
+        }
+    }
+
+    impl<'a, F> BackwardSearcher<'a, F>
+    where
+        F: ArcosphereFamily,
+    {
+        pub(super) fn new(_family: F, recipes: &'a [F::Recipe]) -> Self {
+            Self { recipes }
+        }
+    }
+
+    impl<F> DirectionSearcher for ForwardSearcher<'_, F>
+    where
+        F: ArcosphereFamily,
+    {
+        type Set = F::Set;
+
+        type Recipe = F::Recipe;
+
+        fn direction(&self) -> &'static str {
+            "forward"
+        }
+
+        fn all_recipes(&self) -> impl Iterator<Item = Self::Recipe> {
+            self.recipes.iter().copied()
+        }
+
+        fn extract_recipe(&self, recipe: Self::Recipe) -> (Self::Set, Self::Set) {
+            (recipe.input(), recipe.output())
+        }
+    }
+
+    impl<F> DirectionSearcher for BackwardSearcher<'_, F>
+    where
+        F: ArcosphereFamily,
+    {
+        type Set = F::Set;
+
+        type Recipe = Reverse<F::Recipe>;
+
+        fn direction(&self) -> &'static str {
+            "backward"
+        }
+
+        fn all_recipes(&self) -> impl Iterator<Item = Self::Recipe> {
+            self.recipes.iter().copied().map(Reverse)
+        }
+
+        fn extract_recipe(&self, recipe: Self::Recipe) -> (Self::Set, Self::Set) {
+            let Reverse(recipe) = recipe;
+
+            recipe.reversed()
+        }
+    }
+} // mod searcher
+
+mod feasibility {
+    //  Returns whether `matrix · x = delta` admits a solution, over the rationals, with `x >= 0`, where `x` is indexed
+    //  by `columns`.
+    //
+    //  Should the computation overflow, the problem is assumed feasible.
+    pub(super) fn is_feasible(matrix: &[Vec<i8>], columns: &[usize], delta: &[i64]) -> bool {
+        Tableau::new(matrix, columns, delta)
+            .and_then(Tableau::solve)
+            .unwrap_or(true)
+    }
+
+    //  Phase 1 of the simplex method: minimizes the sum of the artificial variables, which is 0 if and only if the
+    //  problem is feasible, using Bland's rule to avoid cycling.
+    //
+    //  To avoid rounding, each row is kept as integers, scaled by an arbitrary positive factor.
+    struct Tableau {
+        //  The constraints, then the objective, each as the coefficients of the variables then the right-hand side.
+        //
+        //  The variables are the columns, then an artificial variable per constraint.
+        rows: Vec<Vec<i64>>,
+        //  The basic variable of each constraint.
+        basis: Vec<usize>,
+    }
+
+    impl Tableau {
+        fn new(matrix: &[Vec<i8>], columns: &[usize], delta: &[i64]) -> Option<Self> {
+            let constraints = delta.len();
+            let artificials = columns.len();
+            let variables = artificials + constraints;
+
+            let mut rows = Vec::with_capacity(constraints + 1);
+
+            for (line, &delta) in matrix.iter().zip(delta) {
+                let mut row = vec![0; variables + 1];
+
+                for (coefficient, &column) in row.iter_mut().zip(columns) {
+                    *coefficient = i64::from(line[column]);
+                }
+
+                row[variables] = delta;
+                rows.push(row);
+            }
+
+            //  The artificial variables form the initial basis, hence the right-hand sides must be non-negative.
+            for (i, row) in rows.iter_mut().enumerate() {
+                if row[variables] < 0 {
+                    row.iter_mut().for_each(|coefficient| *coefficient = -*coefficient);
+                }
+
+                row[artificials + i] = 1;
+            }
+
+            let mut objective = vec![0i64; variables + 1];
+
+            for row in &rows {
+                for (total, &coefficient) in objective.iter_mut().zip(row) {
+                    *total = total.checked_sub(coefficient)?;
+                }
+            }
+
+            objective[artificials..variables].fill(0);
+            rows.push(objective);
+
+            let basis = (artificials..variables).collect();
+
+            Some(Self { rows, basis })
+        }
+
+        fn solve(mut self) -> Option<bool> {
+            let constraints = self.basis.len();
+            let rhs = self.rows[constraints].len() - 1;
+
+            loop {
+                let objective = &self.rows[constraints];
+
+                let Some(entering) = (0..rhs).find(|&column| objective[column] < 0) else {
+                    return Some(objective[rhs] == 0);
+                };
+
+                let mut leaving: Option<usize> = None;
+
+                for (i, row) in self.rows[..constraints].iter().enumerate() {
+                    if row[entering] <= 0 {
+                        continue;
+                    }
+
+                    let Some(best) = leaving else {
+                        leaving = Some(i);
+                        continue;
+                    };
+
+                    //  Compares the ratios by cross-multiplication, as both denominators are positive.
+                    let (ratio, best_ratio) = (
+                        row[rhs].checked_mul(self.rows[best][entering])?,
+                        self.rows[best][rhs].checked_mul(row[entering])?,
+                    );
+
+                    if ratio < best_ratio || (ratio == best_ratio && self.basis[i] < self.basis[best]) {
+                        leaving = Some(i);
+                    }
+                }
+
+                //  The objective is bounded below by 0, hence a leaving variable always exists.
+                let leaving = leaving?;
+
+                self.pivot(leaving, entering)?;
+                self.basis[leaving] = entering;
+            }
+        }
+
+        fn pivot(&mut self, leaving: usize, entering: usize) -> Option<()> {
+            let pivot = self.rows[leaving].clone();
+
+            for (i, row) in self.rows.iter_mut().enumerate() {
+                if i == leaving || row[entering] == 0 {
+                    continue;
+                }
+
+                let factor = row[entering];
+                let mut divisor = 0;
+
+                for (coefficient, &p) in row.iter_mut().zip(&pivot) {
+                    *coefficient = coefficient
+                        .checked_mul(pivot[entering])?
+                        .checked_sub(factor.checked_mul(p)?)?;
+                    divisor = num_integer::gcd(divisor, *coefficient);
+                }
+
+                if divisor > 1 {
+                    row.iter_mut().for_each(|coefficient| *coefficient /= divisor);
+                }
+            }
+
+            Some(())
+        }
+    }
+} // mod feasibility
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        executor::DefaultExecutor,
+        model::Path,
+        space_exploration::{SeArcosphereFamily, SeArcosphereRecipe, SeArcosphereSet, SeStagedPath},
+    };
+
+    use super::*;
+
+    #[test]
+    fn size() {
+        //  Mostly the sets of arcospheres, whose counts are u16, see `Count`.
+        assert_eq!(52, core::mem::size_of::<Searcher<SeArcosphereFamily>>());
+    }
+
+    #[test]
+    fn dynamic_family() {
+        use crate::{
+            dynamic::{DynArcosphereDefinition, DynFamily, DynFamilyDefinition, DynFamilyError, DynRecipe},
+            model::{Arcosphere, ArcosphereRecipe},
+            space_exploration::SeArcosphere,
+        };
+
+        let arcospheres = (0..SeArcosphere::DIMENSION)
+            .map(SeArcosphere::from_index)
+            .map(|sphere| DynArcosphereDefinition {
+                abbr: sphere.abbr(),
+                full: sphere.full().to_string(),
+                fancy: Some(sphere.fancy().to_string()),
+                polarity: sphere.polarity(),
+            })
+            .collect();
+
+        let recipes = SeArcosphereRecipe::all()
+            .map(|recipe| (recipe.input().to_string(), recipe.output().to_string()))
+            .collect();
+
+        let definition = DynFamilyDefinition { arcospheres, recipes };
+
+        assert_eq!(
+            Err(DynFamilyError::DimensionMismatch { expected: 7, actual: 8 }),
+            DynFamily::<7>::install(definition.clone())
+        );
+
+        let mut invalid = definition.clone();
+        invalid.arcospheres[0].abbr = '"';
+
+        assert_eq!(
+            Err(DynFamilyError::InvalidAbbreviation('"')),
+            DynFamily::<8>::install(invalid)
+        );
+
+        let family = DynFamily::<8>::install(definition.clone()).expect("success");
+
+        assert_eq!(Ok(family), DynFamily::<8>::install(definition.clone()));
+        assert_eq!(&definition, family.definition());
+
+        //  Only the recipes of the definition exist.
+        let recipes: Vec<_> = DynRecipe::<8>::all().map(|recipe| recipe.to_string()).collect();
+        let expected: Vec<_> = SeArcosphereRecipe::all().map(|recipe| recipe.to_string()).collect();
+
+        assert_eq!(expected, recipes);
+        assert_eq!(None, DynRecipe::<8>::try_from_index(SeArcosphereRecipe::DIMENSION));
+
+        let mut different = definition;
+        different.recipes.pop();
+
+        assert_eq!(
+            Err(DynFamilyError::AlreadyInstalled),
+            DynFamily::<8>::install(different)
+        );
+
+        let expected: Vec<_> = solve("EP".parse().unwrap(), "LX".parse().unwrap())
+            .iter()
+            .map(|path| path.to_string())
+            .collect();
+
+        let paths: Vec<_> = Solver::<_, DefaultExecutor>::new(family)
+            .with_configuration(SolverConfiguration {
+                maximum_catalysts: 2,
+                ..Default::default()
+            })
+            .solve("EP".parse().unwrap(), "LX".parse().unwrap())
+            .expect("success")
+            .iter()
+            .map(|path| path.to_string())
+            .collect();
+
+        assert_eq!(expected, paths);
+    }
+
+    #[test]
+    fn configuration_presets() {
+        let (quick, default, exhaustive) = (
+            SolverConfiguration::quick(),
+            SolverConfiguration::default(),
+            SolverConfiguration::exhaustive(),
+        );
+
+        for configuration in [quick, exhaustive] {
+            let SolverConfiguration {
+                minimum_catalysts,
+                maximum_catalysts,
+                maximum_repetitions,
+                maximum_stages,
+                ..
+            } = configuration;
+
+            let built = SolverConfiguration::builder()
+                .catalyst_range(minimum_catalysts, maximum_catalysts)
+                .maximum_repetitions(maximum_repetitions)
+                .maximum_stages(maximum_stages)
+                .build();
+
+            assert!(built.is_ok(), "{configuration:?}");
+        }
+
+        assert!(quick.maximum_catalysts < default.maximum_catalysts);
+        assert!(quick.maximum_recipes < default.maximum_recipes);
+
+        assert!(exhaustive.maximum_catalysts > default.maximum_catalysts);
+        assert!(exhaustive.maximum_recipes > default.maximum_recipes);
+
+        let paths = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(quick)
+            .solve("EP".parse().unwrap(), "LX".parse().unwrap())
+            .expect("success");
+
+        assert!(!paths.is_empty());
+    }
+
+    #[test]
+    fn configuration_builder() {
+        let configuration = SolverConfiguration::builder()
+            .catalyst_range(1, 3)
+            .maximum_recipes(12)
+            .build()
+            .expect("valid");
+
+        let expected = SolverConfiguration {
+            minimum_catalysts: 1,
+            maximum_catalysts: 3,
+            maximum_recipes: 12,
+            ..SolverConfiguration::default()
+        };
+
+        assert_eq!(expected, configuration);
+
+        let result = SolverConfiguration::builder().catalyst_range(3, 1).build();
+
+        assert_eq!(Err(ConfigurationError::InvertedCatalysts), result);
+
+        let result = SolverConfiguration::builder().maximum_repetitions(0).build();
+
+        assert_eq!(Err(ConfigurationError::NoRepetitions), result);
+
+        let result = SolverConfiguration::builder().maximum_stages(0).build();
+
+        assert_eq!(Err(ConfigurationError::NoStages), result);
+    }
+
+    #[test]
+    fn polarity_step() {
+        let recipes: Vec<_> = SeArcosphereRecipe::all().collect();
+
+        assert_eq!(8, SeSolver::<DefaultExecutor>::polarity_step(&recipes));
+
+        let folding: Vec<_> = recipes.iter().copied().filter(|r| r.input().len() == 2).collect();
+
+        assert_eq!(0, SeSolver::<DefaultExecutor>::polarity_step(&folding));
+
+        assert!(SeSolver::<DefaultExecutor>::is_polarity_reachable(16, 8));
+        assert!(!SeSolver::<DefaultExecutor>::is_polarity_reachable(4, 8));
+        assert!(!SeSolver::<DefaultExecutor>::is_polarity_reachable(4, 0));
+
+        //  Only the restriction of recipes prevents the inversion.
+        let source = "LL".parse().unwrap();
+        let target = "OZ".parse().unwrap();
+
+        let solver = SeSolver::<DefaultExecutor>::default();
+
+        assert!(!solver.is_polarity_restricted(source, target));
+
+        let solver = solver.with_forbidden_recipes([SeArcosphereRecipe::GOTZ, SeArcosphereRecipe::ELPX]);
+
+        assert!(solver.is_polarity_restricted(source, target));
+        assert!(!solver.is_polarity_restricted("EP".parse().unwrap(), "LX".parse().unwrap()));
+    }
+
+    #[test]
+    fn solve_zero() {
+        let set = "EL".parse().unwrap();
+
+        let expected = vec![StagedPath {
+            path: Path {
+                source: set,
+                target: set,
+                count: ONE,
+                catalysts: SeArcosphereSet::new(),
+                recipes: Vec::new(),
+            },
+            stages: Vec::new(),
+        }];
+
+        let paths = solve(set, set);
+
+        assert_eq!(expected, paths);
+    }
+
+    #[test]
+    fn solve_one() {
+        let source = "EO".parse().unwrap();
+        let target = "LG".parse().unwrap();
+
+        let expected = vec![StagedPath {
+            path: Path {
+                source,
+                target,
+                count: ONE,
+                catalysts: SeArcosphereSet::new(),
+                recipes: vec![SeArcosphereRecipe::EO],
+            },
+            stages: Vec::new(),
+        }];
+
+        let paths = solve(source, target);
+
+        assert_eq!(expected, paths);
+    }
+
+    #[test]
+    fn solve_space_folding_data_a() {
+        let source = "EP".parse().unwrap();
+        let target = "LX".parse().unwrap();
+
+        //  Without extra catalysts.
+        {
+            let no_extra_catalysts = SolverConfiguration {
+                extra_catalysts: 0,
+                ..Default::default()
+            };
+
+            let catalysts_g = "G".parse().unwrap();
+            let catalysts_o = "O".parse().unwrap();
+
+            let expected = vec![
+                StagedPath {
+                    path: Path {
+                        source,
+                        target,
+                        count: ONE,
+                        catalysts: catalysts_g,
+                        recipes: vec![SeArcosphereRecipe::PG, SeArcosphereRecipe::EO],
+                    },
+                    stages: vec![1],
+                },
+                StagedPath {
+                    path: Path {
+                        source,
+                        target,
+                        count: ONE,
+                        catalysts: catalysts_o,
+                        recipes: vec![SeArcosphereRecipe::EO, SeArcosphereRecipe::PG],
+                    },
+                    stages: vec![1],
+                },
+            ];
+
+            let paths = solve_with(source, target, no_extra_catalysts);
+
+            assert_eq!(expected, paths);
+        }
+
+        //  With extra catalysts.
+        {
+            let catalysts_go = "GO".parse().unwrap();
+
+            let expected = vec![
+                StagedPath {
+                    path: Path {
+                        source,
+                        target,
+                        count: ONE,
+                        catalysts: catalysts_go,
+                        recipes: vec![SeArcosphereRecipe::EO, SeArcosphereRecipe::PG],
+                    },
+                    stages: vec![],
+                },
+            ];
+
+            let paths = solve(source, target);
+
+            assert_eq!(expected, paths);
+        }
+    }
+
+    #[test]
+    fn solve_space_dilation_data_a() {
+        const TWO: NonZeroU8 = NonZeroU8::new(2).unwrap();
+
+        let source = "LL".parse().unwrap();
+        let target = "OZ".parse().unwrap();
+
+        let catalysts_pg = "PG".parse().unwrap();
+        let catalysts_xo = "XO".parse().unwrap();
+
+        let inversion = SeArcosphereRecipe::ELPX;
+
+        let et = SeArcosphereRecipe::ET;
+        let lo = SeArcosphereRecipe::LO;
+        let lt = SeArcosphereRecipe::LT;
+        let pg = SeArcosphereRecipe::PG;
+        let xz = SeArcosphereRecipe::XZ;
+
+        let expected: Vec<StagedPath<_>> = vec![
+            StagedPath {
+                path: Path {
+                    source,
+                    target,
+                    count: TWO,
+                    catalysts: catalysts_pg,
+                    recipes: vec![pg, lo, lt, xz, inversion, lt, et],
+                },
+                stages: vec![1, 2, 3, 4, 6],
+            },
+            StagedPath {
+                path: Path {
+                    source,
+                    target,
+                    count: TWO,
+                    catalysts: catalysts_xo,
+                    recipes: vec![lo, lt, xz, inversion, lt, et, pg],
+                },
+                stages: vec![1, 2, 3, 5, 6],
+            },
+        ];
+
+        let paths = solve(source, target);
+
+        assert_eq!(expected, paths);
+    }
+
+    #[test]
+    fn solve_space_injection_data_a() {
+        const TWO: NonZeroU8 = NonZeroU8::new(2).unwrap();
+
+        let source = "ZZ".parse().unwrap();
+        let target = "GT".parse().unwrap();
+
+        //  Without extra catalysts.
+        {
+            let no_extra_catalysts = SolverConfiguration {
+                extra_catalysts: 0,
+                ..Default::default()
+            };
+
+            let catalysts = "X".parse().unwrap();
+
+            let xz = SeArcosphereRecipe::XZ;
+            let pz = SeArcosphereRecipe::PZ;
+            let et = SeArcosphereRecipe::ET;
+            let pg = SeArcosphereRecipe::PG;
+            let eo = SeArcosphereRecipe::EO;
+            let lo = SeArcosphereRecipe::LO;
+
+            let expected = vec![StagedPath {
+                path: Path {
+                    source,
+                    target,
+                    count: TWO,
+                    catalysts,
+                    recipes: vec![xz, pz, et, pg, xz, pz, eo, lo],
+                },
+                stages: vec![1, 2, 3, 4, 5, 6, 7],
+            }];
+
+            let paths = solve_with(source, target, no_extra_catalysts);
+
+            assert_eq!(expected, paths);
+        }
+
+        //  With extra catalysts.
+        {
+            let catalysts = "PX".parse().unwrap();
+
+            let xz = SeArcosphereRecipe::XZ;
+            let pz = SeArcosphereRecipe::PZ;
+            let et = SeArcosphereRecipe::ET;
+            let pg = SeArcosphereRecipe::PG;
+            let eo = SeArcosphereRecipe::EO;
+            let lo = SeArcosphereRecipe::LO;
+
+            let expected = vec![StagedPath {
+                path: Path {
+                    source,
+                    target,
+                    count: TWO,
+                    catalysts,
+                    recipes: vec![pz, xz, et, pz, eo, pg, lo, xz],
+                },
+                stages: vec![2, 4, 6],
+            }];
+
+            let paths = solve(source, target);
+
+            assert_eq!(expected, paths);
+        }
+    }
+
+    #[test]
+    fn solve_space_warping_data_b() {
+        const TWO: NonZeroU8 = NonZeroU8::new(2).unwrap();
+
+        let source = "GO".parse().unwrap();
+        let target = "EP".parse().unwrap();
+
+        let catalysts_lx = "LX".parse().unwrap();
+        let catalysts_tz = "TZ".parse().unwrap();
+
+        let inversion = SeArcosphereRecipe::GOTZ;
+
+        let lo = SeArcosphereRecipe::LO;
+        let lt = SeArcosphereRecipe::LT;
+        let xg = SeArcosphereRecipe::XG;
+        let xz = SeArcosphereRecipe::XZ;
+
+        let expected = vec![
+            StagedPath {
+                path: Path {
+                    source,
+                    target,
+                    count: TWO,
+                    catalysts: catalysts_lx,
+                    recipes: vec![lo, xg, lt, xz, inversion],
+                },
+                stages: vec![2, 4],
+            },
+            StagedPath {
+                path: Path {
+                    source,
+                    target,
+                    count: TWO,
+                    catalysts: catalysts_tz,
+                    recipes: vec![inversion, lo, xg, lt, xz],
+                },
+                stages: vec![1, 3],
+            },
+        ];
+
+        let paths = solve(source, target);
+
+        assert_eq!(expected, paths);
+    }
+
+    #[test]
+    fn solve_stats() {
+        let source = "EP".parse().unwrap();
+        let target = "LX".parse().unwrap();
+
+        let (paths, stats) = SeSolver::<DefaultExecutor>::default()
+            .solve_with_stats(source, target)
+            .expect("success");
+
+        assert!(!paths.is_empty());
+        assert_ne!(0, stats.counts_tried);
+        assert_ne!(0, stats.catalysts_tried);
+        assert_ne!(0, stats.forward_expanded);
+        assert_ne!(0, stats.peak_forward);
+    }
+
+    #[test]
+    fn solve_cost() {
+        let source = "EP".parse().unwrap();
+        let target = "LX".parse().unwrap();
+
+        //  With a uniform cost, the number of stages no longer matters, only the number of recipes does.
+        fn cost(_: &SeArcosphereRecipe) -> u32 {
+            1
+        }
+
+        let configuration = SolverConfiguration {
+            maximum_catalysts: 2,
+            ..Default::default()
+        };
+
+        let paths = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(configuration)
+            .with_cost(cost)
+            .solve(source, target)
+            .expect("success");
+
+        assert!(paths.iter().all(|p| p.path.recipes.len() == 2));
+
+        //  The single-stage path is no longer preferred over the two-stages ones.
+        let catalysts: Vec<SeArcosphereSet> = paths.iter().map(|p| p.path.catalysts).collect();
+
+        assert!(catalysts.contains(&"G".parse().unwrap()), "{catalysts:?}");
+        assert!(catalysts.contains(&"GO".parse().unwrap()), "{catalysts:?}");
+    }
+
+    #[test]
+    fn solve_count_overflow() {
+        use crate::space_exploration::SeArcosphere::{self, *};
+
+        let set = |counts: &[(SeArcosphere, u16)]| SeArcosphereSet::from_counts(counts.iter().copied());
+
+        //  With 255 repetitions, the 257 Epsilons exactly fit, but adding any Epsilon catalyst overflows.
+        let source = set(&[(Epsilon, 257), (Phi, 1)]);
+        let target = set(&[(Epsilon, 256), (Theta, 1), (Zeta, 1)]);
+
+        for batch_catalysts in [false, true] {
+            let configuration = SolverConfiguration {
+                maximum_catalysts: 1,
+                maximum_repetitions: 255,
+                maximum_recipes: 1,
+                batch_catalysts,
+                ..SolverConfiguration::default()
+            };
+
+            let result = SeSolver::<DefaultExecutor>::default()
+                .with_configuration(configuration)
+                .solve(source, target);
+
+            assert!(result.is_err(), "{result:?}");
+        }
+    }
+
+    #[test]
+    fn solve_maximum_stages() {
+        let source = "EP".parse().unwrap();
+        let target = "TZ".parse().unwrap();
+
+        //  All the paths from EP to TZ, with the fewest catalysts, have 3 stages.
+        let paths = SeSolver::<DefaultExecutor>::default()
+            .solve(source, target)
+            .expect("success");
+
+        assert!(paths.iter().all(|p| p.stage_count() == 3), "{paths:?}");
+
+        //  With fewer stages allowed, more catalysts are required.
+        let configuration = SolverConfiguration {
+            maximum_stages: 2,
+            ..Default::default()
+        };
+
+        let paths = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(configuration)
+            .solve(source, target)
+            .expect("success");
+
+        assert!(!paths.is_empty());
+        assert!(paths.iter().all(|p| p.stage_count() <= 2), "{paths:?}");
+        assert!(paths.iter().all(|p| p.path.catalysts.len() == 4), "{paths:?}");
+
+        //  Unless they are not allowed either, in which case the stages are the blocker.
+        let configuration = SolverConfiguration {
+            maximum_catalysts: 3,
+            maximum_stages: 2,
+            ..Default::default()
+        };
+
+        let result = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(configuration)
+            .solve(source, target);
+
+        assert_eq!(Err(ResolutionError::OutsideStages { maximum_stages: 2 }), result);
+    }
+
+    #[test]
+    fn solve_cache() {
+        let source = "EP".parse().unwrap();
+        let target = "LX".parse().unwrap();
+
+        let cache = Arc::new(MemoryCache::new());
+
+        let solver = SeSolver::<DefaultExecutor>::default().with_cache(cache.clone());
+
+        let paths = solver.solve(source, target).expect("success");
+
+        let key = solver.cache_key(source, target);
+
+        assert_eq!(1, cache.len());
+        assert_eq!(Some(paths.clone()), cache.get(&key));
+
+        //  The cached solutions are returned as is, without searching anew.
+        cache.put(key, Vec::new());
+
+        assert_eq!(Ok(Vec::new()), solver.solve(source, target));
+
+        //  A different configuration is a different entry.
+        let configuration = SolverConfiguration {
+            maximum_recipes: 10,
+            ..Default::default()
+        };
+
+        let other = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(configuration)
+            .with_cache(cache.clone())
+            .solve(source, target);
+
+        assert_eq!(Ok(paths), other);
+        assert_eq!(2, cache.len());
+
+        //  Restricting the recipes, or setting a cost or scarcity function, is a different entry as well.
+        let forbidden = solver
+            .clone()
+            .with_forbidden_recipes([SeArcosphereRecipe::GOTZ, SeArcosphereRecipe::ELPX]);
+
+        assert_ne!(Ok(Vec::new()), forbidden.solve(source, target));
+        assert_eq!(3, cache.len());
+
+        let costly = solver.clone().with_cost(|_| 2);
+
+        assert_ne!(Ok(Vec::new()), costly.solve(source, target));
+        assert_eq!(4, cache.len());
+
+        let scarce = solver.clone().with_scarcity(|_| 2);
+
+        assert_ne!(Ok(Vec::new()), scarce.solve(source, target));
+        assert_eq!(5, cache.len());
+
+        //  Functions are keyed by their values, hence a scarcity function equivalent to the default shares its entry.
+        let uniform = solver.clone().with_scarcity(|_| 1);
+
+        assert_eq!(Ok(Vec::new()), uniform.solve(source, target));
+        assert_eq!(5, cache.len());
+    }
+
+    #[test]
+    fn solve_scarcity() {
+        use crate::space_exploration::SeArcosphere;
+
+        let source = "EP".parse().unwrap();
+        let target = "LX".parse().unwrap();
+
+        //  Gamma is scarce, whereas Omega is plentiful.
+        fn scarcity(a: SeArcosphere) -> u32 {
+            match a {
+                SeArcosphere::Gamma => 10,
+                _ => 1,
+            }
+        }
+
+        let configuration = SolverConfiguration {
+            objective: Objective::LowestScarcity,
+            ..Default::default()
+        };
+
+        let paths = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(configuration)
+            .with_scarcity(scarcity)
+            .solve(source, target)
+            .expect("success");
+
+        let catalysts: Vec<SeArcosphereSet> = paths.iter().map(|p| p.path.catalysts).collect();
+
+        let omega: SeArcosphereSet = "O".parse().unwrap();
+
+        assert!(!catalysts.is_empty());
+        assert!(catalysts.iter().all(|c| *c == omega), "{catalysts:?}");
+
+        //  Without the objective, the scarcity is ignored.
+        let paths = SeSolver::<DefaultExecutor>::default()
+            .with_scarcity(scarcity)
+            .solve(source, target)
+            .expect("success");
+
+        let catalysts: Vec<SeArcosphereSet> = paths.iter().map(|p| p.path.catalysts).collect();
+
+        let gamma = SeArcosphere::Gamma;
+
+        assert!(catalysts.iter().any(|c| c.contains(gamma)), "{catalysts:?}");
+    }
+
+    #[test]
+    fn solve_all() {
+        let source = "EP".parse().unwrap();
+        let target = "LX".parse().unwrap();
+
+        let solver = SeSolver::<DefaultExecutor>::default();
+
+        let all = solver.solve_all(source, target).expect("success");
+        let best = solver.solve(source, target).expect("success");
+
+        assert_eq!(&best[..], &all[..best.len()]);
+
+        //  The longer paths, discarded by `solve`, follow the best ones.
+        let shortest = best[0].recipe_count();
+
+        assert!(all.len() > best.len());
+        assert!(all[best.len()..].iter().all(|p| !best.contains(p)));
+        assert!(all.iter().any(|p| p.recipe_count() > shortest));
+
+        let deduplicated: HashSet<_> = all.iter().cloned().collect();
+
+        assert_eq!(all.len(), deduplicated.len());
+    }
+
+    #[test]
+    fn solve_with_catalysts() {
+        let source = "EP".parse().unwrap();
+        let target = "LX".parse().unwrap();
+
+        let solver = SeSolver::<DefaultExecutor>::default();
+
+        let expected = solver.solve(source, target).expect("success");
+        let catalysts = expected[0].path.catalysts;
+
+        let paths = solver.solve_with_catalysts(source, target, catalysts).expect("success");
+
+        assert_eq!(expected, paths);
+
+        let result = solver.solve_with_catalysts(source, target, "EE".parse().unwrap());
+
+        assert_eq!(Err(ResolutionError::OutsideCount { maximum_repetitions: 4 }), result);
+
+        let result = solver.solve_with_catalysts(source, "L".parse().unwrap(), catalysts);
+
+        assert_eq!(Err(ResolutionError::PreservationError), result);
+    }
+
+    #[test]
+    fn solve_until() {
+        use crate::{space_exploration::SeArcosphere, verifier::Verifier};
+
+        let source = "EO".parse().unwrap();
+        let two = NonZeroU8::new(2).unwrap();
+
+        let solver = SeSolver::<DefaultExecutor>::default();
+
+        let staged = solver
+            .solve_until(source, two, |set| set[SeArcosphere::Lambda] >= 2)
+            .expect("success");
+
+        assert_eq!("EEOO", staged.path.source.to_string());
+        assert_eq!("GGLL", staged.path.target.to_string());
+        assert_eq!(2, staged.recipe_count());
+
+        Verifier::new(SeArcosphereFamily).verify(&staged).expect("valid");
+
+        //  The source itself may satisfy the predicate.
+        let staged = solver.solve_until(source, ONE, |_| true).expect("success");
+
+        assert_eq!(0, staged.recipe_count());
+
+        //  Or no set may.
+        let result = solver.solve_until(source, ONE, |set| set.is_empty());
+
+        assert_eq!(Err(SolverConfiguration::default().outside_recipes()), result);
+    }
+
+    #[test]
+    fn solve_superset() {
+        let solver = SeSolver::<DefaultExecutor>::default();
+
+        let solve =
+            |source: &str, target: &str| solver.solve_superset(source.parse().unwrap(), target.parse().unwrap());
+
+        assert_eq!(Err(ResolutionError::PreservationError), solve("L", "LO"));
+
+        let trivial = solve("LO", "L").expect("success");
+
+        assert_eq!(1, trivial.len());
+        assert_eq!("LO -> LO  =>  ", trivial[0].to_string());
+
+        let single = solve("LO", "T").expect("success");
+
+        assert_eq!(1, single.len());
+        assert_eq!("LO -> TX  =>  LO -> TX", single[0].to_string());
+
+        let paths = solve("EP", "L").expect("success");
+
+        let target: SeArcosphereSet = "L".parse().unwrap();
+        let verifier = crate::verifier::Verifier::new(SeArcosphereFamily);
+
+        for path in &paths {
+            assert!(target.is_subset_of(&path.path.target), "{path}");
+
+            verifier.verify(path).expect("valid");
+        }
+    }
+
+    #[test]
+    fn reachable() {
+        let source: SeArcosphereSet = "LO".parse().unwrap();
+
+        let none = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(SolverConfiguration {
+                maximum_recipes: 0,
+                ..SolverConfiguration::default()
+            })
+            .reachable(source);
+
+        assert_eq!(HashSet::from_iter([source]), none);
+
+        let one = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(SolverConfiguration {
+                maximum_recipes: 1,
+                ..SolverConfiguration::default()
+            })
+            .reachable(source);
+
+        assert_eq!(HashSet::from_iter([source, "TX".parse().unwrap()]), one);
+
+        let all = SeSolver::<DefaultExecutor>::default().reachable(source);
+
+        assert!(all.is_superset(&one));
+        assert!(all.iter().all(|set| set.len() == 2));
+
+        //  Catalysts are required to fold EP into LX, as in `solve`.
+        let source: SeArcosphereSet = "EP".parse().unwrap();
+        let target = "LX".parse().unwrap();
+
+        let without = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(SolverConfiguration {
+                maximum_catalysts: 0,
+                ..SolverConfiguration::default()
+            })
+            .reachable(source);
+
+        assert_eq!(HashSet::from_iter([source]), without);
+
+        let with = SeSolver::<DefaultExecutor>::default().reachable(source);
+
+        assert!(with.contains(&target));
+        assert!(with.iter().all(|set| set.len() == 2));
+    }
+
+    #[test]
+    fn solve_prefer_fewer_catalyst_types() {
+        let source = "OTX".parse().unwrap();
+        let target = "LOT".parse().unwrap();
+
+        let diversity = |path: &SeStagedPath| {
+            let mut spheres: Vec<_> = path.path.catalysts.into_iter().collect();
+            spheres.dedup();
+
+            spheres.len()
+        };
+
+        let default = SeSolver::<DefaultExecutor>::default()
+            .solve(source, target)
+            .expect("success");
+
+        let preferred = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(SolverConfiguration {
+                prefer_fewer_catalyst_types: true,
+                ..SolverConfiguration::default()
+            })
+            .solve(source, target)
+            .expect("success");
+
+        assert_ne!(default, preferred);
+
+        let default: HashSet<_> = default.into_iter().collect();
+
+        assert_eq!(default, preferred.iter().cloned().collect());
+        assert!(preferred.is_sorted_by_key(diversity));
+    }
+
+    #[test]
+    fn solve_one_first() {
+        use crate::verifier::Verifier;
+
+        let solver = SeSolver::<DefaultExecutor>::default();
+
+        let source = "EP".parse().unwrap();
+        let target = "LX".parse().unwrap();
+
+        let path = solver.solve_one(source, target).expect("success");
+
+        Verifier::new(SeArcosphereFamily).verify(&path).expect("valid");
+
+        let all = solver.solve_all(source, target).expect("success");
+
+        let minimum = all.iter().map(|p| p.path.catalysts.len()).min();
+
+        assert_eq!(Some(path.path.catalysts.len()), minimum);
+
+        let result = solver.solve_one("L".parse().unwrap(), "LO".parse().unwrap());
+
+        assert_eq!(Err(ResolutionError::PreservationError), result);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn solve_reproducible() {
+        use crate::executor::{RayonExecutor, SequentialExecutor};
+
+        const PROBLEMS: [(&str, &str); 3] = [("EP", "LX"), ("LL", "OZ"), ("EO", "LG")];
+
+        let sequential = SeSolver::<SequentialExecutor>::default();
+        let rayon = SeSolver::<RayonExecutor>::default();
+
+        for (source, target) in PROBLEMS {
+            let (source, target) = (source.parse().unwrap(), target.parse().unwrap());
+
+            assert_eq!(sequential.solve(source, target), rayon.solve(source, target));
+            assert_eq!(sequential.solve_one(source, target), rayon.solve_one(source, target));
+            assert_eq!(sequential.solve_all(source, target), rayon.solve_all(source, target));
+        }
+    }
+
+    #[test]
+    fn solve_total_order() {
+        const PROBLEMS: [(&str, &str); 3] = [("EP", "LX"), ("LL", "OZ"), ("EO", "LG")];
+
+        let solver = SeSolver::<DefaultExecutor>::default();
+
+        for (source, target) in PROBLEMS {
+            let (source, target) = (source.parse().unwrap(), target.parse().unwrap());
+
+            let all = solver.solve_all(source, target).expect("success");
+
+            //  The order of paths is total, as the reproducibility of the order of the paths found depends on it.
+            for a in &all {
+                for b in &all {
+                    assert_eq!(a == b, a.cmp(b).is_eq(), "{a} vs {b}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn solve_batch_catalysts() {
+        const PROBLEMS: [(&str, &str); 3] = [("EP", "LX"), ("LL", "OZ"), ("EO", "LG")];
+
+        let one_at_a_time = SeSolver::<DefaultExecutor>::default();
+        let batch = SeSolver::<DefaultExecutor>::default().with_configuration(SolverConfiguration {
+            batch_catalysts: true,
+            ..SolverConfiguration::default()
+        });
+
+        for (source, target) in PROBLEMS {
+            let (source, target) = (source.parse().unwrap(), target.parse().unwrap());
+
+            assert_eq!(one_at_a_time.solve(source, target), batch.solve(source, target));
+            assert_eq!(one_at_a_time.solve_all(source, target), batch.solve_all(source, target));
+        }
+
+        let restricted = SolverConfiguration {
+            maximum_catalysts: 0,
+            ..SolverConfiguration::default()
+        };
+
+        let (source, target) = ("EP".parse().unwrap(), "LX".parse().unwrap());
+
+        let expected = one_at_a_time.with_configuration(restricted).solve(source, target);
+
+        let result = batch
+            .with_configuration(SolverConfiguration {
+                batch_catalysts: true,
+                ..restricted
+            })
+            .solve(source, target);
+
+        assert!(expected.is_err());
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn solve_concurrent_directions() {
+        let configuration = SolverConfiguration::builder()
+            .concurrent_directions(true)
+            .build()
+            .expect("valid");
+
+        let serial = SeSolver::<DefaultExecutor>::default();
+        let concurrent = SeSolver::<DefaultExecutor>::default().with_configuration(configuration);
+
+        for (source, target) in [("EP", "LX"), ("LL", "OZ"), ("OTX", "LOT")] {
+            let (source, target) = (source.parse().unwrap(), target.parse().unwrap());
+
+            assert_eq!(serial.solve(source, target), concurrent.solve(source, target));
+        }
+    }
+
+    #[test]
+    fn solve_with_midpoints() {
+        let solver = SeSolver::<DefaultExecutor>::default();
+
+        let trivial = solver.solve_with_midpoints("EO".parse().unwrap(), "LG".parse().unwrap());
+
+        assert_eq!(Ok(vec![("EO -> LG  =>  EO -> LG".parse().unwrap(), None)]), trivial);
+
+        let paths = solver
+            .solve_with_midpoints("EP".parse().unwrap(), "LX".parse().unwrap())
+            .expect("success");
+
+        assert_ne!(0, paths.len());
+
+        for (path, midpoint) in paths {
+            let midpoint = midpoint.expect("midpoint");
+
+            let source = path.path.source * path.path.count + path.path.catalysts;
+            let target = path.path.target * path.path.count + path.path.catalysts;
+
+            assert_eq!(source.len(), midpoint.len(), "{path}");
+            assert!(solver.reachable(source).contains(&midpoint), "{path}");
+            assert!(solver.reachable(midpoint).contains(&target), "{path}");
+        }
+    }
+
+    #[test]
+    fn solve_iter() {
+        let source = "EP".parse().unwrap();
+        let target = "LX".parse().unwrap();
+
+        let solver = SeSolver::<DefaultExecutor>::default().with_configuration(SolverConfiguration {
+            maximum_catalysts: 2,
+            ..Default::default()
+        });
+
+        let mut solutions = solver.solve_iter(source, target);
+
+        //  Discovery order: the paths with the fewest catalysts come first.
+        let first = solutions.next().expect("a path");
+
+        assert_eq!(1, first.path.catalysts.len());
+
+        let rest: Vec<_> = solutions.by_ref().collect();
+
+        assert_eq!(None, solutions.error());
+        assert!(rest.iter().any(|p| p.path.catalysts.len() == 2));
+
+        //  Unfiltered, hence more paths than `solve` returns.
+        let best = solver.solve(source, target).expect("success");
+
+        assert!(best.len() < rest.len() + 1);
+    }
+
+    #[test]
+    fn solve_forbidden() {
+        let inversions = [SeArcosphereRecipe::GOTZ, SeArcosphereRecipe::ELPX];
+
+        let solver = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(SolverConfiguration {
+                maximum_catalysts: 2,
+                ..Default::default()
+            })
+            .with_forbidden_recipes(inversions);
+
+        //  Pure folding.
+        {
+            let source = "EP".parse().unwrap();
+            let target = "LX".parse().unwrap();
+
+            let paths = solver.solve(source, target).expect("success");
+
+            let uses_inversion = |p: &SeStagedPath| p.path.recipes.iter().any(|r| inversions.contains(r));
+
+            assert!(!paths.is_empty());
+            assert!(!paths.iter().any(uses_inversion));
+        }
+
+        //  Inversion required.
+        {
+            let source = "LL".parse().unwrap();
+            let target = "OZ".parse().unwrap();
+
+            let result = solver.solve(source, target);
+
+            assert_eq!(Err(ResolutionError::Infeasible), result);
+        }
+    }
+
+    #[test]
+    fn solve_allowed() {
+        let source = "EO".parse().unwrap();
+        let target = "LG".parse().unwrap();
+
+        //  The single-recipe path is not available.
+        let allowed = [SeArcosphereRecipe::PG, SeArcosphereRecipe::XG];
+
+        let result = SeSolver::<DefaultExecutor>::default()
+            .with_allowed_recipes(Some(&allowed))
+            .solve(source, target);
+
+        assert_eq!(Err(ResolutionError::Infeasible), result);
+
+        //  The recipes of the unrestricted paths are enough.
+        let source = "EP".parse().unwrap();
+        let target = "LX".parse().unwrap();
+
+        let paths = solve(source, target);
+        let allowed: Vec<_> = paths.iter().flat_map(|p| p.path.recipes.iter().copied()).collect();
+
+        let result = SeSolver::<DefaultExecutor>::default()
+            .with_allowed_recipes(Some(&allowed))
+            .solve(source, target);
+
+        assert_eq!(Ok(paths), result);
+
+        //  The restriction does not hide the actual error, when it does not prune the search space by itself.
+        let configuration = SolverConfiguration {
+            maximum_catalysts: 0,
+            ..Default::default()
+        };
+
+        let unrestricted = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(configuration)
+            .solve(source, target);
+
+        let restricted = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(configuration)
+            .with_allowed_recipes(Some(&allowed))
+            .solve(source, target);
+
+        assert!(unrestricted.is_err());
+        assert_eq!(unrestricted, restricted);
+    }
+
+    #[test]
+    fn is_feasible() {
+        //  A single recipe, turning the first arcosphere into the second.
+        let matrix = [vec![-1], vec![1]];
+
+        assert!(feasibility::is_feasible(&matrix, &[0], &[-2, 2]));
+        assert!(feasibility::is_feasible(&matrix, &[0], &[0, 0]));
+        assert!(!feasibility::is_feasible(&matrix, &[0], &[2, -2]));
+        assert!(!feasibility::is_feasible(&matrix, &[], &[-2, 2]));
+
+        //  All changes preserving the number of arcospheres are feasible, with all recipes.
+        let solver = SeSolver::<DefaultExecutor>::default();
+
+        for (source, target) in [("EP", "LX"), ("LL", "OZ"), ("ZZ", "GT"), ("GO", "EP"), ("EEE", "TTT")] {
+            let (source, target) = (source.parse().unwrap(), target.parse().unwrap());
+
+            assert!(solver.is_feasible(source, target), "{source} -> {target}");
+        }
+    }
+
+    #[test]
+    fn solve_best_so_far() {
+        let source = "EP".parse().unwrap();
+        let target = "LX".parse().unwrap();
+
+        let solver = SeSolver::<DefaultExecutor>::default();
+
+        let (paths, error) = solver.solve_iter(source, target).best_so_far();
 
-        let paths = solve(source, target);
+        assert_eq!(None, error);
+        assert_eq!(solve(source, target), paths);
 
-        assert_eq!(expected, paths);
+        //  Cancelled before finding anything.
+        let cancellation = Cancellation::new();
+        cancellation.cancel();
+
+        let (paths, error) = solver
+            .with_cancellation(cancellation)
+            .solve_iter(source, target)
+            .best_so_far();
+
+        assert_eq!(Some(ResolutionError::Cancelled), error);
+        assert!(paths.is_empty());
     }
 
     #[test]
-    fn solve_space_folding_data_a() {
+    fn solve_with_available_catalysts() {
         let source = "EP".parse().unwrap();
         let target = "LX".parse().unwrap();
 
-        //  Without extra catalysts.
-        {
-            let no_extra_catalysts = SolverConfiguration {
-                extra_catalysts: 0,
-                ..Default::default()
-            };
+        let solver = SeSolver::<DefaultExecutor>::default();
 
-            let catalysts_g = "G".parse().unwrap();
-            let catalysts_o = "O".parse().unwrap();
+        let available: SeArcosphereSet = "GO".parse().unwrap();
 
-            let expected = vec![
-                StagedPath {
-                    path: Path {
-                        source,
-                        target,
-                        count: ONE,
-                        catalysts: catalysts_g,
-                        recipes: vec![SeArcosphereRecipe::PG, SeArcosphereRecipe::EO],
-                    },
-                    stages: vec![1],
-                },
-                StagedPath {
-                    path: Path {
-                        source,
-                        target,
-                        count: ONE,
-                        catalysts: catalysts_o,
-                        recipes: vec![SeArcosphereRecipe::EO, SeArcosphereRecipe::PG],
-                    },
-                    stages: vec![1],
-                },
-            ];
+        //  Only some of the best paths draw their catalysts from the available ones.
+        let expected: Vec<_> = solve(source, target)
+            .into_iter()
+            .filter(|p| available.is_superset_of(&p.path.catalysts))
+            .collect();
 
-            let paths = solve_with(source, target, no_extra_catalysts);
+        assert!(!expected.is_empty());
 
-            assert_eq!(expected, paths);
-        }
+        let paths = solver
+            .solve_with_available_catalysts(source, target, available)
+            .expect("success");
 
-        //  With extra catalysts.
-        {
-            let catalysts_go = "GO".parse().unwrap();
+        assert_eq!(expected, paths);
 
-            let expected = vec![
-                StagedPath {
-                    path: Path {
-                        source,
-                        target,
-                        count: ONE,
-                        catalysts: catalysts_go,
-                        recipes: vec![SeArcosphereRecipe::EO, SeArcosphereRecipe::PG],
-                    },
-                    stages: vec![],
-                },
-            ];
+        //  No catalyst available at all.
+        let result = solver.solve_with_available_catalysts(source, target, SeArcosphereSet::new());
 
-            let paths = solve(source, target);
+        assert_eq!(Err(ResolutionError::OutsideCount { maximum_repetitions: 4 }), result);
 
-            assert_eq!(expected, paths);
-        }
-    }
+        //  Only the subsets of each number of catalysts are generated, up to the maximum number of permutations.
+        let subsets = Searcher::<SeArcosphereFamily>::generate_available_catalysts("GGO".parse().unwrap(), 2, 2);
 
-    #[test]
-    fn solve_space_dilation_data_a() {
-        const TWO: NonZeroU8 = NonZeroU8::new(2).unwrap();
+        assert_eq!(Some(vec!["GG".parse().unwrap(), "GO".parse().unwrap()]), subsets);
 
-        let source = "LL".parse().unwrap();
-        let target = "OZ".parse().unwrap();
+        let result = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(SolverConfiguration {
+                maximum_catalyst_permutations: 1,
+                ..SolverConfiguration::default()
+            })
+            .solve_with_available_catalysts(source, target, available);
 
-        let catalysts_pg = "PG".parse().unwrap();
-        let catalysts_xo = "XO".parse().unwrap();
+        let outside_permutations = ResolutionError::OutsidePermutations {
+            maximum_catalyst_permutations: 1,
+        };
 
-        let inversion = SeArcosphereRecipe::ELPX;
+        assert_eq!(Err(outside_permutations), result);
 
-        let et = SeArcosphereRecipe::ET;
-        let lo = SeArcosphereRecipe::LO;
-        let lt = SeArcosphereRecipe::LT;
-        let pg = SeArcosphereRecipe::PG;
-        let xz = SeArcosphereRecipe::XZ;
+        //  As any search, it may be cancelled, or time out, yielding the paths found so far.
+        let cancellation = Cancellation::new();
+        cancellation.cancel();
 
-        let expected: Vec<StagedPath<_>> = vec![
-            StagedPath {
-                path: Path {
-                    source,
-                    target,
-                    count: TWO,
-                    catalysts: catalysts_pg,
-                    recipes: vec![pg, lo, lt, xz, inversion, lt, et],
-                },
-                stages: vec![1, 2, 3, 4, 6],
-            },
-            StagedPath {
-                path: Path {
-                    source,
-                    target,
-                    count: TWO,
-                    catalysts: catalysts_xo,
-                    recipes: vec![lo, lt, xz, inversion, lt, et, pg],
-                },
-                stages: vec![1, 2, 3, 5, 6],
-            },
-        ];
+        let (paths, error) = solver
+            .with_cancellation(cancellation)
+            .solve_iter_with_available_catalysts(source, target, available)
+            .best_so_far();
 
-        let paths = solve(source, target);
+        assert_eq!(Some(ResolutionError::Cancelled), error);
+        assert!(paths.is_empty());
+
+        let result = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(SolverConfiguration {
+                time_budget: Some(Duration::ZERO),
+                ..SolverConfiguration::default()
+            })
+            .solve_with_available_catalysts(source, target, available);
+
+        assert_eq!(Err(ResolutionError::Timeout), result);
+
+        //  Batching explores the same subsets.
+        let paths = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(SolverConfiguration {
+                batch_catalysts: true,
+                ..SolverConfiguration::default()
+            })
+            .solve_with_available_catalysts(source, target, available)
+            .expect("success");
 
         assert_eq!(expected, paths);
     }
 
     #[test]
-    fn solve_space_injection_data_a() {
-        const TWO: NonZeroU8 = NonZeroU8::new(2).unwrap();
+    fn solve_deepening() {
+        let deepening = SolverConfiguration {
+            deepening_step: 2,
+            ..Default::default()
+        };
 
-        let source = "ZZ".parse().unwrap();
-        let target = "GT".parse().unwrap();
+        for (source, target) in [("EP", "LX"), ("LL", "OZ"), ("ZZ", "GT"), ("GO", "EP")] {
+            let source = source.parse().unwrap();
+            let target = target.parse().unwrap();
 
-        //  Without extra catalysts.
-        {
-            let no_extra_catalysts = SolverConfiguration {
-                extra_catalysts: 0,
-                ..Default::default()
-            };
+            let expected = solve(source, target);
 
-            let catalysts = "X".parse().unwrap();
+            assert_eq!(expected, solve_with(source, target, deepening), "{source} -> {target}");
+        }
+    }
 
-            let xz = SeArcosphereRecipe::XZ;
-            let pz = SeArcosphereRecipe::PZ;
-            let et = SeArcosphereRecipe::ET;
-            let pg = SeArcosphereRecipe::PG;
-            let eo = SeArcosphereRecipe::EO;
-            let lo = SeArcosphereRecipe::LO;
+    #[test]
+    fn solve_cancelled() {
+        let source = "EP".parse().unwrap();
+        let target = "LX".parse().unwrap();
 
-            let expected = vec![StagedPath {
-                path: Path {
-                    source,
-                    target,
-                    count: TWO,
-                    catalysts,
-                    recipes: vec![xz, pz, et, pg, xz, pz, eo, lo],
-                },
-                stages: vec![1, 2, 3, 4, 5, 6, 7],
-            }];
+        let cancellation = Cancellation::new();
+        cancellation.cancel();
 
-            let paths = solve_with(source, target, no_extra_catalysts);
+        let result = SeSolver::<DefaultExecutor>::default()
+            .with_cancellation(cancellation)
+            .solve(source, target);
 
-            assert_eq!(expected, paths);
-        }
+        assert_eq!(Err(ResolutionError::Cancelled), result);
+    }
 
-        //  With extra catalysts.
-        {
-            let catalysts = "PX".parse().unwrap();
+    #[test]
+    fn solve_timeout() {
+        let source = "EP".parse().unwrap();
+        let target = "LX".parse().unwrap();
 
-            let xz = SeArcosphereRecipe::XZ;
-            let pz = SeArcosphereRecipe::PZ;
-            let et = SeArcosphereRecipe::ET;
-            let pg = SeArcosphereRecipe::PG;
-            let eo = SeArcosphereRecipe::EO;
-            let lo = SeArcosphereRecipe::LO;
+        let timed = |budget| {
+            SeSolver::<DefaultExecutor>::default().with_configuration(SolverConfiguration {
+                time_budget: Some(budget),
+                ..SolverConfiguration::default()
+            })
+        };
 
-            let expected = vec![StagedPath {
-                path: Path {
-                    source,
-                    target,
-                    count: TWO,
-                    catalysts,
-                    recipes: vec![pz, xz, et, pz, eo, pg, lo, xz],
-                },
-                stages: vec![2, 4, 6],
-            }];
+        //  Timed out before finding anything.
+        let solver = timed(Duration::ZERO);
 
-            let paths = solve(source, target);
+        assert_eq!(Err(ResolutionError::Timeout), solver.solve(source, target));
+        assert_eq!(Err(ResolutionError::Timeout), solver.solve_superset(source, target));
 
-            assert_eq!(expected, paths);
-        }
+        let (paths, error) = solver.solve_iter(source, target).best_so_far();
+
+        assert_eq!(Some(ResolutionError::Timeout), error);
+        assert!(paths.is_empty());
+
+        //  Not timed out.
+        let solver = timed(Duration::from_secs(3600));
+
+        assert_eq!(Ok(solve(source, target)), solver.solve(source, target));
     }
 
     #[test]
-    fn solve_space_warping_data_b() {
-        const TWO: NonZeroU8 = NonZeroU8::new(2).unwrap();
-
-        let source = "GO".parse().unwrap();
-        let target = "EP".parse().unwrap();
+    fn solve_resumable() {
+        let source = "ZZ".parse().unwrap();
+        let target = "GT".parse().unwrap();
 
-        let catalysts_lx = "LX".parse().unwrap();
-        let catalysts_tz = "TZ".parse().unwrap();
+        let configuration = SolverConfiguration {
+            maximum_catalysts: 2,
+            ..SolverConfiguration::default()
+        };
 
-        let inversion = SeArcosphereRecipe::GOTZ;
+        let solver = SeSolver::<DefaultExecutor>::default().with_configuration(SolverConfiguration {
+            maximum_recipes: 2,
+            ..configuration
+        });
 
-        let lo = SeArcosphereRecipe::LO;
-        let lt = SeArcosphereRecipe::LT;
-        let xg = SeArcosphereRecipe::XG;
-        let xz = SeArcosphereRecipe::XZ;
+        let Err((error, state)) = solver.solve_resumable(source, target) else {
+            panic!("Expected failure");
+        };
 
-        let expected = vec![
-            StagedPath {
-                path: Path {
-                    source,
-                    target,
-                    count: TWO,
-                    catalysts: catalysts_lx,
-                    recipes: vec![lo, xg, lt, xz, inversion],
-                },
-                stages: vec![2, 4],
-            },
-            StagedPath {
-                path: Path {
-                    source,
-                    target,
-                    count: TWO,
-                    catalysts: catalysts_tz,
-                    recipes: vec![inversion, lo, xg, lt, xz],
-                },
-                stages: vec![1, 3],
-            },
-        ];
+        assert_eq!(ResolutionError::OutsideRecipes { maximum_recipes: 2 }, error);
 
-        let paths = solve(source, target);
+        let result = solver.with_configuration(configuration).resume(state).expect("success");
 
-        assert_eq!(expected, paths);
+        assert_eq!(solve(source, target), result);
     }
 
     fn solve(source: SeArcosphereSet, target: SeArcosphereSet) -> Vec<SeStagedPath> {
@@ -1020,7 +4394,7 @@ mod tests {
 
             assert_eq!(expected, catalysts.len(), "{n}: {catalysts:?}");
 
-            let deduplicated: FxHashSet<_> = catalysts.iter().copied().collect();
+            let deduplicated: HashSet<_> = catalysts.iter().copied().collect();
 
             assert_eq!(
                 catalysts.len(),
@@ -1030,7 +4404,117 @@ mod tests {
         }
     }
 
+    #[test]
+    fn resolution_error_suggested_config() {
+        let source = "ZZ".parse().unwrap();
+        let target = "GT".parse().unwrap();
+
+        let mut configuration = SolverConfiguration {
+            maximum_catalysts: 2,
+            maximum_recipes: 2,
+            ..SolverConfiguration::default()
+        };
+
+        let paths = loop {
+            let solver = SeSolver::<DefaultExecutor>::default().with_configuration(configuration);
+
+            match solver.solve(source, target) {
+                Ok(paths) => break paths,
+                Err(error) => configuration = error.suggested_config(configuration).expect("suggestion"),
+            }
+        };
+
+        assert!(configuration.maximum_recipes > 2);
+        assert_eq!(solve(source, target), paths);
+
+        let saturated = SolverConfiguration {
+            maximum_recipes: u8::MAX,
+            ..SolverConfiguration::default()
+        };
+
+        let outside_recipes = ResolutionError::OutsideRecipes { maximum_recipes: 20 };
+
+        assert_eq!(None, ResolutionError::Infeasible.suggested_config(configuration));
+        assert_eq!(None, ResolutionError::Cancelled.suggested_config(configuration));
+        assert_eq!(None, outside_recipes.suggested_config(saturated));
+
+        let suggested = outside_recipes.suggested_config(SolverConfiguration::default());
+
+        assert_eq!(Some(24), suggested.map(|c| c.maximum_recipes));
+
+        let outside_catalysts = ResolutionError::OutsideCatalysts { maximum_catalysts: 4 };
+        let suggested = outside_catalysts.suggested_config(SolverConfiguration::default());
+
+        assert_eq!(Some(5), suggested.map(|c| c.maximum_catalysts));
+
+        let timed = SolverConfiguration {
+            time_budget: Some(Duration::from_secs(3)),
+            ..SolverConfiguration::default()
+        };
+
+        let suggested = ResolutionError::Timeout.suggested_config(timed);
+
+        assert_eq!(Some(Some(Duration::from_secs(6))), suggested.map(|c| c.time_budget));
+        let untimed = SolverConfiguration::default();
+
+        assert_eq!(None, ResolutionError::Timeout.suggested_config(untimed));
+    }
+
+    #[test]
+    fn catalysts_limit() {
+        let result = Searcher::<SeArcosphereFamily>::generate_catalysts(2, 35);
+
+        assert_eq!(None, result);
+
+        let result = Searcher::<SeArcosphereFamily>::generate_catalysts(2, 36).map(|c| c.len());
+
+        assert_eq!(Some(36), result);
+
+        //  Requires 2 catalysts.
+        let source = "OTX".parse().unwrap();
+        let target = "LOT".parse().unwrap();
+
+        let result = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(SolverConfiguration {
+                maximum_catalyst_permutations: 35,
+                ..SolverConfiguration::default()
+            })
+            .solve(source, target);
+
+        let outside_permutations = ResolutionError::OutsidePermutations {
+            maximum_catalyst_permutations: 35,
+        };
+
+        assert_eq!(Err(outside_permutations), result);
+
+        //  Likewise when exploring all numbers of catalysts at once.
+        let result = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(SolverConfiguration {
+                maximum_catalyst_permutations: 35,
+                batch_catalysts: true,
+                ..SolverConfiguration::default()
+            })
+            .solve(source, target);
+
+        assert_eq!(Err(outside_permutations), result);
+
+        let configuration = SolverConfiguration {
+            maximum_catalyst_permutations: 35,
+            ..SolverConfiguration::default()
+        };
+
+        let suggested = outside_permutations.suggested_config(configuration).expect("raised");
+
+        assert_eq!(70, suggested.maximum_catalyst_permutations);
+
+        let result = SeSolver::<DefaultExecutor>::default()
+            .with_configuration(suggested)
+            .solve(source, target);
+
+        assert!(result.is_ok_and(|paths| paths.iter().all(|p| p.path.catalysts.len() == 2)));
+    }
+
     fn generate_catalysts(n: usize) -> Vec<SeArcosphereSet> {
-        Searcher::<SeArcosphereFamily>::generate_catalysts(n)
+        Searcher::<SeArcosphereFamily>::generate_catalysts(n, usize::MAX).expect("no limit")
     }
 } // mod tests
@@ -2,7 +2,7 @@
 
 use core::{fmt, str};
 
-use crate::model::{Arcosphere, ArcosphereFamily, ArcosphereRecipe, Path, RecipeParseError, Set, StagedPath};
+use crate::model::{Arcosphere, ArcosphereFamily, ArcosphereRecipe, Path, Polarity, RecipeParseError, Set, StagedPath};
 
 /// Set of arcospheres for Space Exploration.
 pub type SeArcosphereSet = Set<SeArcosphere>;
@@ -39,6 +39,10 @@ impl fmt::Display for SeArcosphere {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         use core::fmt::Write;
 
+        if f.alternate() {
+            return f.write_str(self.fancy());
+        }
+
         f.write_char(self.abbr())
     }
 }
@@ -106,6 +110,13 @@ impl const Arcosphere for SeArcosphere {
             Self::Zeta => "ζ",
         }
     }
+
+    fn polarity(&self) -> Polarity {
+        match *self {
+            Self::Epsilon | Self::Lambda | Self::Phi | Self::Xi => Polarity::Negative,
+            Self::Gamma | Self::Omega | Self::Theta | Self::Zeta => Polarity::Positive,
+        }
+    }
 }
 
 /// Space Exploration default recipes.
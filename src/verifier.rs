@@ -5,10 +5,14 @@
 //! 1.  Verifying that all recipes do, in fact, exist.
 //! 2.  Verifying that applying the recipes in the path do indeed lead from source to target, returning the catalysts
 //!     back.
+//!
+//! The simulation, on the other hand, applies the recipes of a path one at a time, for step-by-step visualization.
 
 use core::{error, fmt};
 
-use crate::model::{ArcosphereFamily, ArcosphereSet, StagedPath};
+use alloc::vec::Vec;
+
+use crate::model::{ArcosphereFamily, ArcosphereRecipe, ArcosphereSet, Path, Stage, StagedPath};
 
 /// Error which may occur during the verification.
 #[derive(Clone, Copy, Debug)]
@@ -16,13 +20,13 @@ pub enum VerificationError<F>
 where
     F: ArcosphereFamily,
 {
-    /// A stage could not be applied due to insufficient spheres.
+    /// A stage, or recipe, could not be applied due to insufficient spheres.
     FailedApplication {
-        /// Index of the stage in the path.
+        /// Index of the stage in the staged path, or of the recipe in the path.
         index: usize,
-        /// State prior to attempting to apply the stage.
+        /// State prior to attempting to apply the stage, or recipe.
         current: F::Set,
-        /// Expected input of the stage.
+        /// Expected input of the stage, or recipe.
         input: F::Set,
     },
     /// Applying all recipes in order did not result in the expected target.
@@ -81,29 +85,324 @@ where
         for (index, stage) in staged.stages().enumerate() {
             let input = stage.input();
 
-            if !input.is_subset_of(&step) {
+            let Some(remainder) = step.checked_sub(input) else {
                 return Err(VerificationError::FailedApplication {
                     index,
                     current: step,
                     input,
                 });
+            };
+
+            step = remainder;
+            stage.output_into(&mut step);
+        }
+
+        Self::verify_result(&staged.path, step)
+    }
+
+    /// Verifies that the path is correct, returning the state after applying each stage alongside.
+    ///
+    /// The trace stops at the first stage which fails to apply, if any, in which case the error reports the state prior
+    /// to attempting to apply it.
+    #[allow(clippy::type_complexity)]
+    pub fn explain<'a>(
+        &self,
+        staged: &'a StagedPath<F>,
+    ) -> (Vec<(Stage<'a, F::Recipe>, F::Set)>, Result<(), VerificationError<F>>) {
+        let mut trace = Vec::new();
+
+        let mut step = staged.path.source * staged.path.count + staged.path.catalysts;
+
+        for (index, stage) in staged.stages().enumerate() {
+            let input = stage.input();
+
+            let Some(remainder) = step.checked_sub(input) else {
+                let error = VerificationError::FailedApplication {
+                    index,
+                    current: step,
+                    input,
+                };
+
+                return (trace, Err(error));
+            };
+
+            step = remainder;
+            stage.output_into(&mut step);
+
+            trace.push((stage, step));
+        }
+
+        (trace, Self::verify_result(&staged.path, step))
+    }
+
+    /// Verifies that the path is correct, collecting all errors.
+    ///
+    /// Whenever a stage fails to apply, its output is assumed to have been produced anyway, so that the following
+    /// stages can still be verified.
+    pub fn verify_all(&self, staged: &StagedPath<F>) -> Result<(), Vec<VerificationError<F>>> {
+        let mut errors = Vec::new();
+
+        let mut step = staged.path.source * staged.path.count + staged.path.catalysts;
+
+        for (index, stage) in staged.stages().enumerate() {
+            let input = stage.input();
+
+            if !step.is_superset_of(&input) {
+                errors.push(VerificationError::FailedApplication {
+                    index,
+                    current: step,
+                    input,
+                });
             }
 
-            step = step - input + stage.output();
+            //  Only consumes what is available, as subtraction saturates.
+            step -= input;
+            stage.output_into(&mut step);
         }
 
-        let target = staged.path.target * staged.path.count;
+        if let Err(error) = Self::verify_result(&staged.path, step) {
+            errors.push(error);
+        }
 
-        if !target.is_subset_of(&step) {
-            return Err(VerificationError::FailedTarget { result: step });
+        if !errors.is_empty() {
+            return Err(errors);
         }
 
-        let remainder = step - target;
+        Ok(())
+    }
+
+    /// Verifies that the path is correct, applying its recipes strictly in order.
+    pub fn verify_path(&self, path: &Path<F>) -> Result<(), VerificationError<F>> {
+        let mut step = path.source * path.count + path.catalysts;
+
+        for (index, recipe) in path.recipes.iter().enumerate() {
+            let input = recipe.input();
+
+            let Some(remainder) = step.checked_sub(input) else {
+                return Err(VerificationError::FailedApplication {
+                    index,
+                    current: step,
+                    input,
+                });
+            };
+
+            step = remainder + recipe.output();
+        }
 
-        if remainder != staged.path.catalysts {
+        Self::verify_result(path, step)
+    }
+
+    //  Verifies that the result of applying all recipes is the target, plus the catalysts.
+    fn verify_result(path: &Path<F>, result: F::Set) -> Result<(), VerificationError<F>> {
+        let target = path.target * path.count;
+
+        let Some(remainder) = result.checked_sub(target) else {
+            return Err(VerificationError::FailedTarget { result });
+        };
+
+        if remainder != path.catalysts {
             return Err(VerificationError::FailedCatalysts { remainder });
         }
 
         Ok(())
     }
 }
+
+/// Simulation of a path, applying one recipe at a time, strictly in order.
+#[derive(Clone, Debug)]
+pub struct Simulation<F>
+where
+    F: ArcosphereFamily,
+{
+    path: Path<F>,
+    state: F::Set,
+    next: usize,
+}
+
+impl<F> Simulation<F>
+where
+    F: ArcosphereFamily,
+{
+    /// Creates a new simulation of the path, starting from its source, times its count, plus its catalysts.
+    pub fn new(path: Path<F>) -> Self {
+        let state = path.source * path.count + path.catalysts;
+
+        Self { path, state, next: 0 }
+    }
+
+    /// Returns the path being simulated.
+    pub fn path(&self) -> &Path<F> {
+        &self.path
+    }
+
+    /// Returns the current state, that is the set of arcospheres after applying the recipes stepped so far.
+    pub fn state(&self) -> F::Set {
+        self.state
+    }
+
+    /// Applies the next recipe, returning it alongside the new state, or `None` once all recipes were applied.
+    ///
+    /// If the next recipe cannot be applied, the state is left untouched, and stepping again fails again.
+    #[allow(clippy::type_complexity)]
+    pub fn step(&mut self) -> Option<Result<(&F::Recipe, F::Set), VerificationError<F>>> {
+        let recipe = self.path.recipes.get(self.next)?;
+
+        let input = recipe.input();
+
+        let Some(remainder) = self.state.checked_sub(input) else {
+            let error = VerificationError::FailedApplication {
+                index: self.next,
+                current: self.state,
+                input,
+            };
+
+            return Some(Err(error));
+        };
+
+        self.state = remainder + recipe.output();
+        self.next += 1;
+
+        Some(Ok((recipe, self.state)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec};
+
+    use crate::space_exploration::{SeArcosphereFamily, SeArcosphereRecipe, SeStagedPath};
+
+    use super::*;
+
+    #[test]
+    fn verifier_explain() {
+        let verifier = Verifier::new(SeArcosphereFamily);
+
+        let path: SeStagedPath = "EP -> LX + G  =>  PG -> XO |  EO -> LG".parse().unwrap();
+
+        let (trace, result) = verifier.explain(&path);
+
+        let states: Vec<_> = trace.iter().map(|(stage, state)| (stage.to_string(), *state)).collect();
+
+        let expected = vec![
+            ("GP -> OX".to_string(), "EOX".parse().unwrap()),
+            ("EO -> GL".to_string(), "GLX".parse().unwrap()),
+        ];
+
+        assert_eq!(expected, states);
+        assert!(result.is_ok());
+
+        let path: SeStagedPath = "EP -> LX  =>  PG -> XO |  EO -> LG".parse().unwrap();
+
+        let (trace, result) = verifier.explain(&path);
+
+        assert!(trace.is_empty());
+        let failed = matches!(result, Err(VerificationError::FailedApplication { index: 0, .. }));
+
+        assert!(failed);
+    }
+
+    #[test]
+    fn verifier_simulation() {
+        let path: SeStagedPath = "EP -> LX + G  =>  PG -> XO |  EO -> LG".parse().unwrap();
+
+        let mut simulation = Simulation::new(path.path);
+
+        assert_eq!("EGP", simulation.state().to_string());
+
+        let mut states = Vec::new();
+
+        while let Some(step) = simulation.step() {
+            let (recipe, state) = step.expect("valid");
+
+            states.push((*recipe, state.to_string()));
+        }
+
+        let expected = vec![
+            (SeArcosphereRecipe::PG, "EOX".to_string()),
+            (SeArcosphereRecipe::EO, "GLX".to_string()),
+        ];
+
+        assert_eq!(expected, states);
+
+        //  Without the catalyst, the first recipe cannot be applied.
+        let path: SeStagedPath = "EP -> LX  =>  PG -> XO |  EO -> LG".parse().unwrap();
+
+        let mut simulation = Simulation::new(path.path);
+
+        for _ in 0..2 {
+            let failed = matches!(
+                simulation.step(),
+                Some(Err(VerificationError::FailedApplication { index: 0, .. }))
+            );
+
+            assert!(failed);
+        }
+
+        assert_eq!("EP", simulation.state().to_string());
+    }
+
+    #[test]
+    fn verifier_verify_all() {
+        let verifier = Verifier::new(SeArcosphereFamily);
+
+        let path: SeStagedPath = "EP -> LX + G  =>  PG -> XO |  EO -> LG".parse().unwrap();
+
+        assert!(verifier.verify_all(&path).is_ok());
+
+        //  Without the catalyst, the first stage fails, and the catalyst is then missing at the end.
+        let path: SeStagedPath = "EP -> LX  =>  PG -> XO |  EO -> LG".parse().unwrap();
+
+        let errors = verifier.verify_all(&path).expect_err("invalid");
+
+        assert_eq!(2, errors.len());
+        assert!(matches!(
+            errors[0],
+            VerificationError::FailedApplication { index: 0, .. }
+        ));
+        let VerificationError::FailedCatalysts { remainder } = errors[1] else {
+            panic!("Expected FailedCatalysts");
+        };
+
+        assert_eq!("G", remainder.to_string());
+    }
+
+    #[test]
+    fn verifier_verify_path() {
+        let verifier = Verifier::new(SeArcosphereFamily);
+
+        let path: SeStagedPath = "EP -> LX + G  =>  PG -> XO |  EO -> LG".parse().unwrap();
+
+        assert!(verifier.verify_path(&path.path).is_ok());
+
+        //  The recipes are applied in order, regardless of the stages, hence swapping them fails.
+        let mut swapped = path.path.clone();
+        swapped.recipes.swap(0, 1);
+
+        let failed = matches!(
+            verifier.verify_path(&swapped),
+            Err(VerificationError::FailedApplication { index: 0, .. })
+        );
+
+        assert!(failed);
+
+        //  Without the second recipe, the target is not reached.
+        let mut truncated = path.path.clone();
+        truncated.recipes.pop();
+
+        let Err(VerificationError::FailedTarget { result }) = verifier.verify_path(&truncated) else {
+            panic!("Expected FailedTarget");
+        };
+
+        assert_eq!("EOX", result.to_string());
+
+        //  The target is reached, at the cost of the catalyst.
+        let path: SeStagedPath = "EG -> GL + O  =>  EO -> GL".parse().unwrap();
+
+        let Err(VerificationError::FailedCatalysts { remainder }) = verifier.verify_path(&path.path) else {
+            panic!("Expected FailedCatalysts");
+        };
+
+        assert_eq!("G", remainder.to_string());
+    }
+} // mod tests